@@ -0,0 +1,25 @@
+//! Checks the content files before building, so a mistake in one is reported with the file and
+//! line it's on instead of when the page loads.
+
+#[allow(dead_code)]
+#[path = "src/content.rs"]
+mod content;
+
+use std::fs;
+
+use content::{PROJECTS_FILE, ProjectsFile, SKILLS_FILE, SkillsFile, parse};
+
+fn check<T: serde::de::DeserializeOwned>(file: &str) {
+    let result = fs::read_to_string(file)
+        .map_err(|err| format!("{file}: {err}"))
+        .and_then(|text| parse::<T>(file, &text).map(|_| ()));
+    if let Err(err) = result {
+        println!("cargo::error={err}");
+    }
+}
+
+fn main() {
+    println!("cargo::rerun-if-changed=content");
+    check::<ProjectsFile>(PROJECTS_FILE);
+    check::<SkillsFile>(SKILLS_FILE);
+}
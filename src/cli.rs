@@ -1,46 +1,258 @@
-use clap::{ColorChoice, Parser, Subcommand};
-
-#[derive(Debug, Clone, Parser)]
-#[command(no_binary_name = true, color = ColorChoice::Always)]
-pub struct Cli {
-    #[command(subcommand)]
-    pub command: Command,
-}
-
-#[derive(Debug, Clone, Subcommand)]
-pub enum Command {
-    #[command(about = "Introduction of myself")]
-    About,
-    #[command(about = "Links to use to get in contact with me")]
-    Contact,
-    #[command(about = "Curated subjects or frameworks I have experience with")]
-    Skills,
-    #[command(about = "Curated list of projects that I've spent a lot of time on")]
-    Projects,
-    #[command(about = "History of inputs to this terminal")]
-    History(HistoryArg),
-    #[command(about = "Custom diff implementation on strings")]
-    Diff,
-    #[command(about = "Conway's Game of Life implementation")]
-    Conway(ConwayArg),
-}
-
-#[derive(Debug, Clone, Parser)]
-pub struct HistoryArg {
-    #[command(subcommand)]
-    pub command: Option<HistorySubcommand>,
-}
-
-#[derive(Debug, Clone, Subcommand)]
-pub enum HistorySubcommand {
-    #[command(about = "Clears all of this terminal's history")]
-    Clear,
-    #[command(about = "Gets the history of this terminal at a specific index.")]
-    Index { num: usize },
-}
-
-#[derive(Debug, Clone, Parser)]
-pub struct ConwayArg {
-    #[arg(long, short, help = "Background explanation for the Game of Life")]
-    pub about: bool,
+use clap::error::ErrorKind;
+use clap::{ArgMatches, Command, Error};
+use yew::Html;
+
+use crate::commands::{REGISTRY, TerminalCommand};
+use crate::utils::tokenize::tokenize;
+
+/// A command parsed from a line of input, ready to run
+#[derive(Clone)]
+pub struct Invocation {
+    pub command: &'static dyn TerminalCommand,
+    pub matches: ArgMatches,
+    /// What the command showed, which is rendered once when it runs so later commands don't
+    /// change it. It's empty until the command runs.
+    pub output: Html,
+}
+
+impl std::fmt::Debug for Invocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Invocation")
+            .field("command", &self.command.name())
+            .field("matches", &self.matches)
+            .finish()
+    }
+}
+
+/// Builds the definition of every registered command, which lines of input are parsed with
+pub fn root_command() -> Command {
+    let mut root = REGISTRY.cli();
+    root.build();
+    root
+}
+
+/// Parses a line of terminal input, keeping the case of argument values intact. Variables are
+/// replaced with the values given by `lookup`.
+pub fn parse_line(
+    line: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<Invocation, Error> {
+    let mut tokens = tokenize(line, lookup).map_err(command_error)?;
+    let path = normalize_command_names(&mut tokens);
+    let mut matches = match REGISTRY.cli().try_get_matches_from(tokens) {
+        Ok(matches) => matches,
+        // `<command> --help` shows the same page as `help <command>`
+        Err(err) if err.kind() == ErrorKind::DisplayHelp => {
+            let help = std::iter::once("help".to_string()).chain(path);
+            REGISTRY.cli().try_get_matches_from(help)?
+        }
+        Err(err) => return Err(err),
+    };
+    let (name, matches) = matches
+        .remove_subcommand()
+        .expect("a subcommand is required");
+    let command = REGISTRY
+        .get(&name)
+        .expect("subcommands are only made for registered commands");
+    Ok(Invocation {
+        command,
+        matches,
+        output: Html::default(),
+    })
+}
+
+/// Creates an error that's reported the same way as a parsing error
+pub fn command_error(err: impl std::fmt::Display) -> Error {
+    REGISTRY.cli().error(ErrorKind::InvalidValue, err)
+}
+
+/// Labels an error from a script with the line it came from. Only the error's message is kept,
+/// without the usage and tips after it.
+pub fn script_error(line: usize, err: &Error) -> Error {
+    let text = err.to_string();
+    let message = text.split("\n\n").next().unwrap_or_default();
+    let message = message.strip_prefix("error: ").unwrap_or(message);
+    command_error(format!("line {line}: {}", message.trim_end()))
+}
+
+/// Gets the example lines for a command, given the names of the subcommands leading to it
+pub fn examples(path: &[&str]) -> Vec<&'static str> {
+    let Some(command) = path.first().and_then(|name| REGISTRY.get(name)) else {
+        return Vec::new();
+    };
+    let prefix = path.join(" ");
+    command
+        .examples()
+        .iter()
+        .copied()
+        .filter(|line| {
+            line.strip_prefix(&prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+        })
+        .collect()
+}
+
+/// Follows a path of subcommand names from the root command, matching them case-insensitively
+pub fn find_command<'a>(root: &'a Command, path: &[String]) -> Option<&'a Command> {
+    path.iter().try_fold(root, |current, name| {
+        current
+            .get_subcommands()
+            .find(|sc| sc.get_name().eq_ignore_ascii_case(name))
+    })
+}
+
+/// Rewrites each token naming a subcommand to the subcommand's canonical name, so that only
+/// command names are matched case-insensitively. Returns the names of the subcommands found.
+fn normalize_command_names(tokens: &mut [String]) -> Vec<String> {
+    let root = root_command();
+    let mut current = &root;
+    let mut path = Vec::new();
+
+    let mut tokens = tokens.iter_mut();
+    while let Some(token) = tokens.next() {
+        if let Some(name) = token.strip_prefix("--") {
+            // skip over the value of a flag, so it isn't mistaken for a command
+            let takes_value = current
+                .get_arguments()
+                .any(|a| a.get_long() == Some(name) && a.get_action().takes_values());
+            if takes_value {
+                tokens.next();
+            }
+            continue;
+        }
+        if token.starts_with('-') {
+            continue;
+        }
+        match current
+            .get_subcommands()
+            .find(|sc| sc.get_name().eq_ignore_ascii_case(token))
+        {
+            Some(sub) => {
+                *token = sub.get_name().to_string();
+                path.push(token.clone());
+                current = sub;
+            }
+            // anything else is a positional value, after which no more commands can follow
+            None => break,
+        }
+    }
+    path
+}
+
+#[test]
+fn test_parse_line_case() {
+    let diff = parse_line(r#"DIFF "Hello World" 'hello world'"#, |_| None).unwrap();
+    assert_eq!(diff.command.name(), "diff");
+    assert_eq!(
+        diff.matches.get_one::<String>("a").map(String::as_str),
+        Some("Hello World")
+    );
+    assert_eq!(
+        diff.matches.get_one::<String>("b").map(String::as_str),
+        Some("hello world")
+    );
+
+    let history = parse_line("History Index $N", |_| Some("2".into())).unwrap();
+    assert_eq!(history.command.name(), "history");
+    let (name, index) = history.matches.subcommand().unwrap();
+    assert_eq!(name, "index");
+    assert_eq!(index.get_one::<usize>("num"), Some(&2));
+    assert!(parse_line("diff 'unterminated", |_| None).is_err());
+}
+
+#[test]
+fn test_parse_help() {
+    let help_path = |line: &str| {
+        let help = parse_line(line, |_| None).unwrap();
+        assert_eq!(help.command.name(), "help");
+        help.matches
+            .get_many::<String>("command")
+            .map(|path| path.cloned().collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+    assert!(help_path("help").is_empty());
+    assert_eq!(help_path("help history index"), ["history", "index"]);
+    assert_eq!(help_path("History Index --help"), ["history", "index"]);
+    assert_eq!(help_path("conway -p lwss -h"), ["conway"]);
+    assert_eq!(help_path("--help"), Vec::<String>::new());
+    // like in a shell, quoting doesn't stop a flag from being read as one
+    assert_eq!(help_path(r#"diff "-h" x"#), ["diff"]);
+
+    // flags after `--`, or where a value is expected, aren't asking for help
+    let diff = parse_line("diff -- --help x", |_| None).unwrap();
+    assert_eq!(diff.command.name(), "diff");
+    assert_eq!(
+        diff.matches.get_one::<String>("a").map(String::as_str),
+        Some("--help")
+    );
+    assert!(parse_line("conway --preset '-h'", |_| None).is_err());
+    let search = parse_line("history search -- '-h'", |_| None).unwrap();
+    assert_eq!(search.command.name(), "history");
+    // a search's pattern is compiled when the line is parsed
+    assert!(parse_line("history search '('", |_| None).is_err());
+
+    let root = root_command();
+    let path = ["HISTORY".to_string(), "index".to_string()];
+    assert_eq!(
+        find_command(&root, &path).map(|c| c.get_name()),
+        Some("index")
+    );
+    assert!(find_command(&root, &["nope".to_string()]).is_none());
+}
+
+#[test]
+fn test_examples_parse() {
+    for command in REGISTRY.commands() {
+        assert!(!command.examples().is_empty(), "{}", command.name());
+        for line in command.examples() {
+            let invocation = parse_line(line, |_| None).unwrap();
+            assert_eq!(invocation.command.name(), command.name(), "{line}");
+        }
+    }
+    assert_eq!(examples(&["history", "index"]), ["history index 0"]);
+}
+
+#[test]
+fn test_script_error() {
+    let err = parse_line("nope", |_| None).unwrap_err();
+    assert_eq!(
+        script_error(3, &err).to_string().lines().next(),
+        Some("error: line 3: unrecognized subcommand 'nope'")
+    );
+    let err = script_error(1, &command_error("alias 'p' not found"));
+    assert_eq!(
+        err.to_string().lines().next(),
+        Some("error: line 1: alias 'p' not found")
+    );
+}
+
+#[test]
+fn test_read_only() {
+    let read_only = |line: &str| {
+        let invocation = parse_line(line, |_| None).unwrap();
+        invocation.command.read_only(&invocation.matches)
+    };
+    for line in [
+        "about",
+        "projects",
+        "history",
+        "history index 0",
+        "alias p",
+        "bind Ctrl-A",
+    ] {
+        assert!(read_only(line), "{line}");
+    }
+    for line in [
+        "history clear",
+        "history delete 0",
+        "history run 0",
+        "alias about=contact",
+        "unalias p",
+        "bind Enter clear-screen",
+        "bind --reset",
+        "set HISTSIZE=0",
+        "source .portfoliorc",
+    ] {
+        assert!(!read_only(line), "{line}");
+    }
 }
@@ -0,0 +1,29 @@
+use clap::ArgMatches;
+use yew::{Html, html};
+
+use crate::commands::{TerminalCommand, TerminalState};
+use crate::components::about::About;
+
+pub struct AboutCommand;
+
+impl TerminalCommand for AboutCommand {
+    fn name(&self) -> &'static str {
+        "about"
+    }
+
+    fn about(&self) -> &'static str {
+        "Introduction of myself"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["about"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn view(&self, _: &ArgMatches, _: &TerminalState) -> Html {
+        html! { <About /> }
+    }
+}
@@ -0,0 +1,131 @@
+use clap::{Arg, ArgMatches, Args, Command, Error, FromArgMatches};
+use yew::{Html, html};
+
+use crate::cli::command_error;
+use crate::commands::{Effect, TerminalCommand, TerminalState, view_with_args};
+use crate::utils::alias_store::parse_definition;
+use crate::utils::autocomplete::{Completion, CompletionContext};
+
+#[derive(Debug, Clone, Args)]
+pub struct AliasArg {
+    #[arg(help = "A definition in the form name=expansion, or the name of an alias to show")]
+    pub definition: Option<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct UnaliasArg {
+    #[arg(help = "Name of the alias to remove")]
+    pub name: String,
+}
+
+/// Lists the defined aliases as completions
+fn complete_aliases(context: &CompletionContext) -> Vec<Completion> {
+    context
+        .aliases
+        .aliases()
+        .map(|(name, expansion)| {
+            Completion::new(name.to_string(), Some(format!("alias for '{expansion}'")))
+        })
+        .collect()
+}
+
+pub struct AliasCommand;
+
+impl TerminalCommand for AliasCommand {
+    fn name(&self) -> &'static str {
+        "alias"
+    }
+
+    fn about(&self) -> &'static str {
+        "Defines a shorthand for a command, or lists the existing ones"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        AliasArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["alias", "alias p=projects", "alias p"]
+    }
+
+    fn read_only(&self, matches: &ArgMatches) -> bool {
+        AliasArg::from_arg_matches(matches)
+            .is_ok_and(|args| !args.definition.is_some_and(|d| d.contains('=')))
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        let Some(definition) = AliasArg::from_arg_matches(matches)?.definition else {
+            return Ok(Effect::None);
+        };
+        if definition.contains('=') {
+            let (name, expansion) = parse_definition(&definition).map_err(command_error)?;
+            state.aliases.set(name.to_string(), expansion.to_string());
+        } else if state.aliases.get(&definition).is_none() {
+            return Err(command_error(format!("alias '{definition}' not found")));
+        }
+        Ok(Effect::None)
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |AliasArg { definition }| {
+            let aliases: Vec<(&str, &str)> = match &definition {
+                Some(name) if !name.contains('=') => state
+                    .aliases
+                    .get(name)
+                    .map(|expansion| vec![(name.as_str(), expansion)])
+                    .unwrap_or_default(),
+                Some(_) => return html! {},
+                None => state.aliases.aliases().collect(),
+            };
+            if aliases.is_empty() {
+                return html! { <p>{"No aliases defined"}</p> };
+            }
+            html! { <ul class="alias-list">
+                { for aliases.into_iter().map(|(name, expansion)| html! {
+                    <li><span class="emph">{name}</span>{format!("='{expansion}'")}</li>
+                }) }
+            </ul> }
+        })
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        complete_aliases(context)
+    }
+}
+
+pub struct UnaliasCommand;
+
+impl TerminalCommand for UnaliasCommand {
+    fn name(&self) -> &'static str {
+        "unalias"
+    }
+
+    fn about(&self) -> &'static str {
+        "Removes a shorthand for a command"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        UnaliasArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["unalias p"]
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        let UnaliasArg { name } = UnaliasArg::from_arg_matches(matches)?;
+        state
+            .aliases
+            .remove(&name)
+            .ok_or_else(|| command_error(format!("alias '{name}' not found")))?;
+        Ok(Effect::None)
+    }
+
+    fn view(&self, _: &ArgMatches, _: &TerminalState) -> Html {
+        html! {}
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        complete_aliases(context)
+    }
+}
@@ -0,0 +1,140 @@
+use clap::{Arg, ArgMatches, Args, Command, Error, FromArgMatches, ValueEnum};
+use yew::{Html, html};
+
+use crate::cli::command_error;
+use crate::commands::{Effect, TerminalCommand, TerminalState, view_with_args};
+use crate::utils::autocomplete::{Completion, CompletionContext};
+use crate::utils::keymap::{KeyAction, KeyChord};
+
+#[derive(Debug, Clone, Args)]
+pub struct BindArg {
+    #[arg(help = "Keys to bind, such as Ctrl-A or Alt-F")]
+    pub chord: Option<String>,
+    #[arg(value_enum, help = "What the keys do")]
+    pub action: Option<KeyAction>,
+    #[arg(
+        long,
+        short,
+        requires = "chord",
+        conflicts_with = "action",
+        help = "Removes the binding for the keys"
+    )]
+    pub remove: bool,
+    #[arg(long, conflicts_with_all = ["chord", "remove"], help = "Restores the default bindings")]
+    pub reset: bool,
+}
+
+pub struct BindCommand;
+
+impl TerminalCommand for BindCommand {
+    fn name(&self) -> &'static str {
+        "bind"
+    }
+
+    fn about(&self) -> &'static str {
+        "Lists or changes the console's key bindings"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        BindArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "bind",
+            "bind Ctrl-A",
+            "bind Ctrl-X clear-screen",
+            "bind --remove Ctrl-X",
+            "bind --reset",
+        ]
+    }
+
+    fn read_only(&self, matches: &ArgMatches) -> bool {
+        BindArg::from_arg_matches(matches)
+            .is_ok_and(|args| args.action.is_none() && !args.remove && !args.reset)
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        let args = BindArg::from_arg_matches(matches)?;
+        if args.reset {
+            state.keymap.reset();
+            return Ok(Effect::None);
+        }
+        let Some(chord) = args.chord else {
+            return Ok(Effect::None);
+        };
+        let chord = KeyChord::parse(&chord).map_err(command_error)?;
+        match args.action {
+            Some(action) => {
+                chord.check_bindable().map_err(command_error)?;
+                state.keymap.bind(&chord, action);
+            }
+            None if args.remove => {
+                state
+                    .keymap
+                    .unbind(&chord)
+                    .ok_or_else(|| command_error(format!("'{chord}' is not bound")))?;
+            }
+            None if state.keymap.get(&chord).is_none() => {
+                return Err(command_error(format!("'{chord}' is not bound")));
+            }
+            None => {}
+        }
+        Ok(Effect::None)
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |args: BindArg| {
+            if args.reset || args.remove || args.action.is_some() {
+                return html! {};
+            }
+            match args.chord.map(|chord| KeyChord::parse(&chord)) {
+                Some(Ok(chord)) => view_bindings(
+                    state
+                        .keymap
+                        .get(&chord)
+                        .map(|action| (chord.to_string(), action)),
+                ),
+                Some(Err(_)) => html! {},
+                None => view_bindings(
+                    state
+                        .keymap
+                        .bindings()
+                        .map(|(chord, action)| (chord.to_string(), action)),
+                ),
+            }
+        })
+    }
+
+    fn complete(&self, _: &[&str], arg: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        if arg.get_id() != "chord" {
+            return Vec::new();
+        }
+        context
+            .keymap
+            .bindings()
+            .map(|(chord, action)| Completion::new(chord.to_string(), Some(action.to_string())))
+            .collect()
+    }
+}
+
+fn view_bindings(bindings: impl IntoIterator<Item = (String, KeyAction)>) -> Html {
+    let bindings: Vec<(String, KeyAction)> = bindings.into_iter().collect();
+    if bindings.is_empty() {
+        return html! { <p>{"No keys are bound"}</p> };
+    }
+    html! { <table class="help-table">
+        { for bindings.into_iter().map(|(chord, action)| html! { <tr>
+            <td class="emph">{chord}</td>
+            <td>{action.to_string()}</td>
+            <td class="ital">{help(action)}</td>
+        </tr> }) }
+    </table> }
+}
+
+fn help(action: KeyAction) -> String {
+    action
+        .to_possible_value()
+        .and_then(|value| value.get_help().map(ToString::to_string))
+        .unwrap_or_default()
+}
@@ -0,0 +1,28 @@
+use clap::{ArgMatches, Error};
+use yew::{Html, html};
+
+use crate::commands::{Effect, TerminalCommand, TerminalState};
+
+pub struct ClearCommand;
+
+impl TerminalCommand for ClearCommand {
+    fn name(&self) -> &'static str {
+        "clear"
+    }
+
+    fn about(&self) -> &'static str {
+        "Clears the terminal's output"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["clear"]
+    }
+
+    fn execute(&self, _: &ArgMatches, _: &mut TerminalState) -> Result<Effect, Error> {
+        Ok(Effect::ClearTranscript)
+    }
+
+    fn view(&self, _: &ArgMatches, _: &TerminalState) -> Html {
+        html! {}
+    }
+}
@@ -0,0 +1,67 @@
+use clap::ArgMatches;
+use yew::{Html, html};
+
+use crate::commands::{TerminalCommand, TerminalState};
+
+/// A place I can be reached, shown under a heading
+pub struct Contact {
+    pub heading: &'static str,
+    pub label: &'static str,
+    pub url: &'static str,
+}
+
+pub const CONTACTS: &[Contact] = &[
+    Contact {
+        heading: "You can send me emails here:",
+        label: "Benjaminjahall@gmail.com",
+        url: "mailto:Benjaminjahall@gmail.com",
+    },
+    Contact {
+        heading: "My projects and code are documented here:",
+        label: "benhall-7",
+        url: "https://github.com/benhall-7",
+    },
+    Contact {
+        heading: "Obligatory LinkedIn:",
+        label: "benjaminjahall",
+        url: "https://www.linkedin.com/in/benjaminjahall/",
+    },
+    Contact {
+        heading: "Check out my observations on iNaturalist:",
+        label: "benhall-7",
+        url: "https://www.inaturalist.org/observations?user_id=benhall-7&verifiable=any",
+    },
+];
+
+pub struct ContactCommand;
+
+impl TerminalCommand for ContactCommand {
+    fn name(&self) -> &'static str {
+        "contact"
+    }
+
+    fn about(&self) -> &'static str {
+        "Links to use to get in contact with me"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["contact"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn view(&self, _: &ArgMatches, _: &TerminalState) -> Html {
+        html! {
+            for CONTACTS.iter().enumerate().map(|(i, contact)| html! { <>
+                if i > 0 {
+                    <br />
+                    <br />
+                }
+                <h2>{contact.heading}</h2>
+                <a href={contact.url}>{contact.label}</a>
+            </> })
+        }
+    }
+}
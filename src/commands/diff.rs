@@ -0,0 +1,43 @@
+use clap::{ArgMatches, Args, Command};
+use yew::{Html, html};
+
+use crate::commands::{TerminalCommand, TerminalState, view_with_args};
+use crate::components::differ::Differ;
+
+#[derive(Debug, Clone, Args)]
+pub struct DiffArg {
+    #[arg(help = "Initial text for the A input")]
+    pub a: Option<String>,
+    #[arg(help = "Initial text for the B input")]
+    pub b: Option<String>,
+}
+
+pub struct DiffCommand;
+
+impl TerminalCommand for DiffCommand {
+    fn name(&self) -> &'static str {
+        "diff"
+    }
+
+    fn about(&self) -> &'static str {
+        "Custom diff implementation on strings"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        DiffArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["diff kitten sitting", "diff 'Hello World' 'hello, world'"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn view(&self, matches: &ArgMatches, _: &TerminalState) -> Html {
+        view_with_args(matches, |DiffArg { a, b }| {
+            html! { <Differ a={a.unwrap_or_default()} b={b.unwrap_or_default()} /> }
+        })
+    }
+}
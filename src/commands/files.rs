@@ -0,0 +1,279 @@
+use clap::{Arg, ArgMatches, Args, Command, Error, FromArgMatches};
+use yew::{Html, html};
+
+use crate::cli::command_error;
+use crate::commands::{Effect, TerminalCommand, TerminalState, view_runnable, view_with_args};
+use crate::utils::autocomplete::{Completion, CompletionContext};
+use crate::utils::vfs::{self, Node};
+
+#[derive(Debug, Clone, Args)]
+pub struct LsArg {
+    #[arg(help = "Directory to list, which is the current one by default")]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CdArg {
+    #[arg(help = "Directory to move to, which is the root by default")]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CatArg {
+    #[arg(required = true, help = "Files to show")]
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct TreeArg {
+    #[arg(help = "Directory to draw, which is the current one by default")]
+    pub path: Option<String>,
+}
+
+/// Lists the paths below the current directory as completions, leaving out files when only
+/// directories are wanted
+fn complete_paths(context: &CompletionContext, files: bool) -> Vec<Completion> {
+    let Some(dir) = vfs::lookup(context.cwd) else {
+        return Vec::new();
+    };
+    let mut completions: Vec<Completion> = vfs::descendants(dir)
+        .into_iter()
+        .filter(|(_, node)| files || node.is_dir())
+        .map(|(path, node)| {
+            let description = if node.is_dir() { "directory" } else { "file" };
+            Completion::new(path, Some(description.to_string()))
+        })
+        .collect();
+    if context.cwd != vfs::ROOT {
+        completions.push(Completion::new(
+            "..".to_string(),
+            Some("parent directory".to_string()),
+        ));
+    }
+    completions
+}
+
+/// Opens a path from `cwd`, reporting a missing path as an error
+fn open(cwd: &str, path: &str) -> Result<(String, &'static Node), Error> {
+    vfs::open(cwd, path).map_err(command_error)
+}
+
+pub struct LsCommand;
+
+impl TerminalCommand for LsCommand {
+    fn name(&self) -> &'static str {
+        "ls"
+    }
+
+    fn about(&self) -> &'static str {
+        "Lists the files in a directory"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        LsArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["ls", "ls projects/prc", "ls /skills"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        let LsArg { path } = LsArg::from_arg_matches(matches)?;
+        open(&state.cwd, path.as_deref().unwrap_or("."))?;
+        Ok(Effect::None)
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |LsArg { path }| {
+            let Ok((path, node)) = vfs::open(&state.cwd, path.as_deref().unwrap_or(".")) else {
+                return html! {};
+            };
+            let entries: Vec<(String, &Node)> = match node {
+                Node::Dir(entries) => entries
+                    .iter()
+                    .map(|(name, node)| (vfs::join(&path, name), node))
+                    .collect(),
+                Node::File(_) => vec![(path, node)],
+            };
+            html! { <ul class="file-list">
+                { for entries.into_iter().map(|(path, node)| {
+                    let name = path.rsplit('/').next().unwrap_or_default().to_string();
+                    let item = if node.is_dir() {
+                        view_runnable(&format!("{name}/"), format!("ls {path}"), &state.onrun)
+                    } else {
+                        view_runnable(&name, format!("cat {path}"), &state.onrun)
+                    };
+                    html! { <li class={node.is_dir().then_some("emph")}>{item}</li> }
+                }) }
+            </ul> }
+        })
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        complete_paths(context, true)
+    }
+}
+
+pub struct CdCommand;
+
+impl TerminalCommand for CdCommand {
+    fn name(&self) -> &'static str {
+        "cd"
+    }
+
+    fn about(&self) -> &'static str {
+        "Moves to another directory"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        CdArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["cd projects", "cd ..", "cd"]
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        let CdArg { path } = CdArg::from_arg_matches(matches)?;
+        let path = path.as_deref().unwrap_or(vfs::ROOT);
+        let (absolute, node) = open(&state.cwd, path)?;
+        if !node.is_dir() {
+            return Err(command_error(format!("{path}: not a directory")));
+        }
+        state.cwd = absolute;
+        Ok(Effect::None)
+    }
+
+    fn view(&self, _: &ArgMatches, _: &TerminalState) -> Html {
+        html! {}
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        complete_paths(context, false)
+    }
+}
+
+pub struct PwdCommand;
+
+impl TerminalCommand for PwdCommand {
+    fn name(&self) -> &'static str {
+        "pwd"
+    }
+
+    fn about(&self) -> &'static str {
+        "Shows the current directory"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["pwd"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn view(&self, _: &ArgMatches, state: &TerminalState) -> Html {
+        html! { <p>{&state.cwd}</p> }
+    }
+}
+
+pub struct CatCommand;
+
+impl TerminalCommand for CatCommand {
+    fn name(&self) -> &'static str {
+        "cat"
+    }
+
+    fn about(&self) -> &'static str {
+        "Shows the contents of files"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        CatArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "cat about.md",
+            "cat projects/prc/prc-rs.md",
+            "cat about.md contact.md",
+        ]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        let CatArg { paths } = CatArg::from_arg_matches(matches)?;
+        for path in &paths {
+            if open(&state.cwd, path)?.1.is_dir() {
+                return Err(command_error(format!("{path}: is a directory")));
+            }
+        }
+        Ok(Effect::None)
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |CatArg { paths }| {
+            html! {
+                for paths.iter().filter_map(|path| match vfs::open(&state.cwd, path) {
+                    Ok((_, Node::File(text))) => Some(html! { <pre class="file">{text}</pre> }),
+                    _ => None,
+                })
+            }
+        })
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        complete_paths(context, true)
+    }
+}
+
+pub struct TreeCommand;
+
+impl TerminalCommand for TreeCommand {
+    fn name(&self) -> &'static str {
+        "tree"
+    }
+
+    fn about(&self) -> &'static str {
+        "Shows everything in a directory as a tree"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        TreeArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["tree", "tree projects"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        let TreeArg { path } = TreeArg::from_arg_matches(matches)?;
+        open(&state.cwd, path.as_deref().unwrap_or("."))?;
+        Ok(Effect::None)
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |TreeArg { path }| {
+            let path = path.unwrap_or_else(|| ".".to_string());
+            match vfs::open(&state.cwd, &path) {
+                Ok((_, node)) => html! { <pre class="file">{vfs::tree(&path, node)}</pre> },
+                Err(_) => html! {},
+            }
+        })
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        complete_paths(context, false)
+    }
+}
@@ -0,0 +1,72 @@
+use clap::{Arg, ArgMatches, Args, Command, Error, FromArgMatches};
+use yew::{Html, html};
+
+use crate::cli::{command_error, find_command, root_command};
+use crate::commands::{Effect, TerminalCommand, TerminalState, view_with_args};
+use crate::components::help::Help;
+use crate::utils::autocomplete::{Completion, CompletionContext};
+
+#[derive(Debug, Clone, Args)]
+pub struct HelpArg {
+    #[arg(help = "The command to show, such as 'history index'")]
+    pub command: Vec<String>,
+}
+
+pub struct HelpCommand;
+
+impl TerminalCommand for HelpCommand {
+    fn name(&self) -> &'static str {
+        "help"
+    }
+
+    fn about(&self) -> &'static str {
+        "Lists the commands, or shows how to use one of them"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        HelpArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["help", "help history index", "conway --help"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn execute(&self, matches: &ArgMatches, _: &mut TerminalState) -> Result<Effect, Error> {
+        let HelpArg { command } = HelpArg::from_arg_matches(matches)?;
+        if find_command(&root_command(), &command).is_none() {
+            return Err(command_error(format!(
+                "no such command '{}'",
+                command.join(" ")
+            )));
+        }
+        Ok(Effect::None)
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |HelpArg { command }| {
+            html! { <Help path={command} onrun={state.onrun.clone()} /> }
+        })
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        // complete the subcommands of the command named so far
+        let root = root_command();
+        let path: Vec<String> = context.given.iter().map(ToString::to_string).collect();
+        let Some(command) = find_command(&root, &path) else {
+            return Vec::new();
+        };
+        command
+            .get_subcommands()
+            .map(|sc| {
+                Completion::new(
+                    sc.get_name().to_string(),
+                    sc.get_about().map(ToString::to_string),
+                )
+            })
+            .collect()
+    }
+}
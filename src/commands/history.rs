@@ -0,0 +1,210 @@
+use clap::{Arg, ArgMatches, Args, Command, Error, FromArgMatches, Subcommand};
+use regex::Regex;
+use yew::{Html, html};
+
+use crate::cli::command_error;
+use crate::commands::{Effect, TerminalCommand, TerminalState, view_runnable, view_with_args};
+use crate::components::history::History;
+use crate::utils::autocomplete::{Completion, CompletionContext};
+use crate::utils::history_store::{EntryRange, HistoryEntry};
+
+#[derive(Debug, Clone, Args)]
+pub struct HistoryArg {
+    #[command(subcommand)]
+    pub command: Option<HistorySubcommand>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum HistorySubcommand {
+    #[command(about = "Clears all of this terminal's history")]
+    Clear,
+    #[command(about = "Gets the history of this terminal at a specific index.")]
+    Index { num: usize },
+    #[command(about = "Shows the entries matching a regular expression")]
+    Search {
+        #[arg(
+            value_parser = Regex::new,
+            help = "Regular expression to look for, like '^conway'"
+        )]
+        pattern: Regex,
+    },
+    #[command(about = "Removes the entry at an index")]
+    Delete { num: usize },
+    #[command(about = "Shows the entries in a range of indices")]
+    Range {
+        #[arg(help = "Indices to show, like 2..5, 2..=4, 3.. or ..5")]
+        range: EntryRange,
+    },
+    #[command(about = "Shows the newest entries")]
+    Last {
+        #[arg(default_value_t = 10, help = "How many entries to show")]
+        count: usize,
+    },
+    #[command(about = "Runs the entry at an index again")]
+    Run { num: usize },
+    #[command(about = "Checks that the saved history could be read")]
+    Check,
+    #[command(about = "Keeps a copy of saved history that couldn't be read, then replaces it")]
+    Backup,
+}
+
+/// Gets the entry at an index, reporting a missing one as an error
+fn entry(state: &TerminalState, num: usize) -> Result<&HistoryEntry, Error> {
+    state
+        .history
+        .entries()
+        .get(num)
+        .ok_or_else(|| command_error(format!("there's no history entry at index {num}")))
+}
+
+/// Shows entries of the history along with their indices
+fn view_entries<'a>(
+    state: &TerminalState,
+    entries: impl Iterator<Item = (usize, &'a HistoryEntry)>,
+) -> Html {
+    let items: Vec<(usize, HistoryEntry)> = entries.map(|(i, e)| (i, e.clone())).collect();
+    html! { <History items={items} onrun={state.onrun.clone()} /> }
+}
+
+pub struct HistoryCommand;
+
+impl TerminalCommand for HistoryCommand {
+    fn name(&self) -> &'static str {
+        "history"
+    }
+
+    fn about(&self) -> &'static str {
+        "History of inputs to this terminal"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        HistoryArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "history",
+            "history clear",
+            "history index 0",
+            "history check",
+            "history backup",
+            "history search '^conway'",
+            "history delete 0",
+            "history range 2..5",
+            "history last 5",
+            "history run 0",
+        ]
+    }
+
+    fn read_only(&self, matches: &ArgMatches) -> bool {
+        HistoryArg::from_arg_matches(matches).is_ok_and(|args| {
+            matches!(
+                args.command,
+                None | Some(
+                    HistorySubcommand::Index { .. }
+                        | HistorySubcommand::Search { .. }
+                        | HistorySubcommand::Range { .. }
+                        | HistorySubcommand::Last { .. }
+                        | HistorySubcommand::Check
+                )
+            )
+        })
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        match HistoryArg::from_arg_matches(matches)?.command {
+            Some(HistorySubcommand::Clear) => state.history.clear(),
+            Some(HistorySubcommand::Backup) if !state.history.back_up() => {
+                return Err(command_error(
+                    "the saved history could be read, so there's nothing to back up",
+                ));
+            }
+            Some(HistorySubcommand::Index { num }) => {
+                entry(state, num)?;
+            }
+            Some(HistorySubcommand::Delete { num }) => {
+                entry(state, num)?;
+                state.history.delete(num);
+            }
+            Some(HistorySubcommand::Run { num }) => {
+                return Ok(Effect::RunLine(entry(state, num)?.input.clone()));
+            }
+            _ => {}
+        }
+        Ok(Effect::None)
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |HistoryArg { command }| match command {
+            Some(HistorySubcommand::Clear) => html! { <p>{"History cleared"}</p> },
+            Some(HistorySubcommand::Index { num }) => view_entries(
+                state,
+                state.history.entries().iter().enumerate().skip(num).take(1),
+            ),
+            Some(HistorySubcommand::Search { pattern }) => view_entries(
+                state,
+                state
+                    .history
+                    .entries()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, entry)| pattern.is_match(&entry.input)),
+            ),
+            Some(HistorySubcommand::Delete { num }) => {
+                html! { <p>{format!("Deleted the history entry at index {num}")}</p> }
+            }
+            Some(HistorySubcommand::Range { range }) => {
+                let entries = state.history.entries();
+                let indices = range.indices(entries.len());
+                view_entries(state, indices.clone().zip(&entries[indices]))
+            }
+            Some(HistorySubcommand::Last { count }) => {
+                let entries = state.history.entries();
+                let start = entries.len().saturating_sub(count);
+                view_entries(state, entries.iter().enumerate().skip(start))
+            }
+            Some(HistorySubcommand::Run { num }) => match state.history.entries().get(num) {
+                Some(entry) => html! { <p class="ital">{&entry.input}</p> },
+                None => html! {},
+            },
+            Some(HistorySubcommand::Check) => match state.history.corrupt() {
+                Some(corrupt) => {
+                    let run = |line: &str| view_runnable(line, line.to_string(), &state.onrun);
+                    html! { <>
+                        <p class="emph">
+                            {format!("warning: the saved history couldn't be read, since {}. ", corrupt.reason)}
+                            {"Nothing new will be saved until it's replaced."}
+                        </p>
+                        <p>
+                            {"Run "}{run("history backup")}{" to keep a copy of it first, or "}
+                            {run("history clear")}{" to replace it."}
+                        </p>
+                    </> }
+                }
+                None => html! {
+                    <p>{format!("The saved history is fine, with {} entries", state.history.entries().len())}</p>
+                },
+            },
+            Some(HistorySubcommand::Backup) => html! {
+                <p>{format!(
+                    "Kept a copy of the saved history under '{}'",
+                    state.history.backup_key().unwrap_or_default()
+                )}</p>
+            },
+            None => view_entries(state, state.history.entries().iter().enumerate()),
+        })
+    }
+
+    fn complete(&self, path: &[&str], arg: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        match (path, arg.get_id().as_str()) {
+            (["history", "index" | "delete" | "run"], "num") => context
+                .history
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, item)| Completion::new(i.to_string(), Some(item.input.clone())))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
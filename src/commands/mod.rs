@@ -0,0 +1,209 @@
+//! The commands the terminal can run. Each command implements [`TerminalCommand`] in its own
+//! module and is listed once in [`REGISTRY`], which the parser, autocomplete and help all read
+//! from.
+
+use std::sync::LazyLock;
+
+use clap::{Arg, ArgMatches, ColorChoice, Command, Error, FromArgMatches};
+use yew::{Callback, Html, MouseEvent, html};
+
+use crate::utils::alias_store::AliasStore;
+use crate::utils::ansi_html::convert;
+use crate::utils::autocomplete::{Completion, CompletionContext};
+use crate::utils::history_store::HistoryStore;
+use crate::utils::keymap::Keymap;
+use crate::utils::variables::Variables;
+
+pub mod about;
+pub mod alias;
+pub mod bind;
+pub mod clear;
+pub mod contact;
+pub mod conway;
+pub mod diff;
+pub mod files;
+pub mod help;
+pub mod history;
+pub mod projects;
+pub mod script;
+pub mod search;
+pub mod skills;
+pub mod variables;
+
+/// Every command the terminal knows, in the order they're listed in help
+pub static REGISTRY: LazyLock<Registry> = LazyLock::new(|| {
+    Registry::new(vec![
+        Box::new(about::AboutCommand),
+        Box::new(contact::ContactCommand),
+        Box::new(skills::SkillsCommand),
+        Box::new(projects::ProjectsCommand),
+        Box::new(projects::ProjectCommand),
+        Box::new(history::HistoryCommand),
+        Box::new(diff::DiffCommand),
+        Box::new(conway::ConwayCommand),
+        Box::new(files::LsCommand),
+        Box::new(files::CdCommand),
+        Box::new(files::PwdCommand),
+        Box::new(files::CatCommand),
+        Box::new(files::TreeCommand),
+        Box::new(search::SearchCommand),
+        Box::new(clear::ClearCommand),
+        Box::new(alias::AliasCommand),
+        Box::new(alias::UnaliasCommand),
+        Box::new(variables::SetCommand),
+        Box::new(variables::UnsetCommand),
+        Box::new(variables::EnvCommand),
+        Box::new(script::SourceCommand),
+        Box::new(script::RcCommand),
+        Box::new(bind::BindCommand),
+        Box::new(help::HelpCommand),
+    ])
+});
+
+/// A command that can be typed into the terminal
+pub trait TerminalCommand: Send + Sync {
+    /// The name the command is run by
+    fn name(&self) -> &'static str;
+
+    /// A short description, shown in help and autocomplete
+    fn about(&self) -> &'static str;
+
+    /// Adds the command's arguments and subcommands to its definition
+    fn args(&self, cmd: Command) -> Command {
+        cmd
+    }
+
+    /// Lines which show how the command is used, including any of its subcommands
+    fn examples(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Whether the command, with these arguments, only shows things without changing the
+    /// terminal's state or anything that's saved. Lines from a URL can only run these commands,
+    /// so a link can't change a visitor's settings.
+    fn read_only(&self, _matches: &ArgMatches) -> bool {
+        false
+    }
+
+    /// Makes any changes to the terminal's state when the command runs. Returning an error marks
+    /// the command as failed, and the error is shown instead of the command's output.
+    fn execute(&self, _matches: &ArgMatches, _state: &mut TerminalState) -> Result<Effect, Error> {
+        Ok(Effect::None)
+    }
+
+    /// Shows the command's output. This is rendered once, right after the command runs, so it
+    /// shows the state as it was then.
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html;
+
+    /// Lists values for one of the command's arguments, beyond the possible values it declares.
+    /// `path` holds the names of the command and any subcommands leading to the argument.
+    fn complete(
+        &self,
+        _path: &[&str],
+        _arg: &Arg,
+        _context: &CompletionContext,
+    ) -> Vec<Completion> {
+        Vec::new()
+    }
+}
+
+/// Changes to the terminal itself that a command can ask for, beyond the state it can change
+/// directly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Effect {
+    None,
+    /// Removes everything in the transcript, including earlier output from the same line
+    ClearTranscript,
+    /// Runs each line of a script after the command, adding their output to the same entry
+    RunScript(String),
+    /// Runs a line after the command, adding its output to the same entry
+    RunLine(String),
+}
+
+/// The parts of the terminal which commands can read and change
+pub struct TerminalState {
+    pub history: HistoryStore,
+    pub aliases: AliasStore,
+    pub variables: Variables,
+    pub keymap: Keymap,
+    /// The directory of the portfolio's filesystem that paths are relative to
+    pub cwd: String,
+    /// Whether the last command that ran succeeded
+    pub last_ok: bool,
+    /// Runs a line as if it was entered, for output that links to other commands
+    pub onrun: Callback<String>,
+    /// Runs a script with a command on each line
+    pub onscript: Callback<String>,
+    /// Counts how many times the user has pressed Ctrl-C, so anything running, like the Game of
+    /// Life, can stop when it changes
+    pub interrupts: usize,
+}
+
+/// The number of times the user has pressed Ctrl-C. The transcript provides it as a context,
+/// since command output is only rendered once, so anything running in it can stop when it changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Interrupts(pub usize);
+
+impl TerminalState {
+    /// Looks up the value of a variable, including the read-only ones kept by the terminal
+    pub fn variable(&self, name: &str) -> Option<String> {
+        match name {
+            "HISTCMD" => Some(self.history.entries().len().to_string()),
+            "LAST_STATUS" => Some(if self.last_ok { "ok" } else { "error" }.to_string()),
+            "?" => Some(if self.last_ok { "0" } else { "1" }.to_string()),
+            "PWD" => Some(self.cwd.clone()),
+            _ => self.variables.get(name).map(ToString::to_string),
+        }
+    }
+}
+
+pub struct Registry {
+    commands: Vec<Box<dyn TerminalCommand>>,
+}
+
+impl Registry {
+    pub fn new(commands: Vec<Box<dyn TerminalCommand>>) -> Self {
+        Registry { commands }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn TerminalCommand> {
+        self.commands().find(|command| command.name() == name)
+    }
+
+    pub fn commands(&self) -> impl Iterator<Item = &dyn TerminalCommand> {
+        self.commands.iter().map(AsRef::as_ref)
+    }
+
+    /// Builds the definition which lines of input are parsed with, with a subcommand for each
+    /// registered command
+    pub fn cli(&self) -> Command {
+        let root = Command::new("portfolio")
+            .no_binary_name(true)
+            .color(ColorChoice::Always)
+            .disable_help_subcommand(true)
+            .subcommand_required(true)
+            .arg_required_else_help(true);
+        root.subcommands(
+            self.commands()
+                .map(|command| command.args(Command::new(command.name()).about(command.about()))),
+        )
+    }
+}
+
+/// Shows the output of a command which reads its arguments into a type, or the error if they
+/// can't be read
+pub fn view_with_args<T: FromArgMatches>(
+    matches: &ArgMatches,
+    view: impl FnOnce(T) -> Html,
+) -> Html {
+    match T::from_arg_matches(matches) {
+        Ok(args) => view(args),
+        Err(err) => convert(&err),
+    }
+}
+
+/// Shows text which runs a line when it's clicked
+pub fn view_runnable(text: &str, line: String, onrun: &Callback<String>) -> Html {
+    let onclick = onrun.reform(move |_: MouseEvent| line.clone());
+    html! { <span class="cmd" onclick={onclick}>{text.to_string()}</span> }
+}
@@ -0,0 +1,294 @@
+use clap::{Arg, ArgMatches, Args, Command, Error, FromArgMatches, ValueEnum};
+use yew::{Html, html};
+
+use crate::cli::command_error;
+use crate::commands::{Effect, TerminalCommand, TerminalState, view_with_args};
+use crate::components::projects::{Project, ProjectDetail, Projects, find_project};
+use crate::content::{PROJECT_GROUPS, ProjectGroupInfo, ProjectInfo, ProjectStatus, slug};
+use crate::utils::autocomplete::{Completion, CompletionContext};
+
+#[derive(Debug, Clone, Args)]
+pub struct ProjectsArg {
+    #[arg(
+        short,
+        long,
+        help = "Only show projects with this tag, which can be given more than once"
+    )]
+    pub tag: Vec<String>,
+    #[arg(short, long, value_enum, help = "Only show projects with this status")]
+    pub status: Option<StatusArg>,
+    #[arg(short, long, help = "Only show projects from this group, like prc")]
+    pub group: Option<String>,
+    #[arg(
+        long,
+        value_enum,
+        help = "Sort the projects in one list, instead of by group"
+    )]
+    pub sort: Option<ProjectSort>,
+}
+
+impl ProjectsArg {
+    fn is_empty(&self) -> bool {
+        self.tag.is_empty() && self.status.is_none() && self.group.is_none() && self.sort.is_none()
+    }
+
+    /// Lists the projects which match every filter, in the order asked for
+    fn projects(&self) -> Vec<&'static ProjectInfo> {
+        self.projects_in(&PROJECT_GROUPS)
+    }
+
+    fn projects_in<'a>(&self, groups: &'a [ProjectGroupInfo]) -> Vec<&'a ProjectInfo> {
+        let mut projects: Vec<&ProjectInfo> = groups
+            .iter()
+            .filter(|group| self.group.as_ref().is_none_or(|name| group.name() == *name))
+            .flat_map(|group| &group.projects)
+            .filter(|project| self.tag.iter().all(|tag| project.has_tag(tag)))
+            .filter(|project| {
+                self.status
+                    .is_none_or(|status| project.status == Some(status.into()))
+            })
+            .collect();
+        match self.sort {
+            // ongoing projects first, then by the year they ended, and any without dates last
+            Some(ProjectSort::Date) => projects.sort_by_key(|project| {
+                let dated = project.started.is_some() || project.ended.is_some();
+                std::cmp::Reverse(
+                    dated.then(|| (project.ended.unwrap_or(u16::MAX), project.started)),
+                )
+            }),
+            Some(ProjectSort::Title) => {
+                projects.sort_by_key(|project| project.title.to_lowercase())
+            }
+            None => {}
+        }
+        projects
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatusArg {
+    #[value(help = "Still being worked on")]
+    Active,
+    #[value(help = "Finished, or no longer worked on")]
+    Archived,
+}
+
+impl From<StatusArg> for ProjectStatus {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Active => ProjectStatus::Active,
+            StatusArg::Archived => ProjectStatus::Archived,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProjectSort {
+    #[value(help = "Most recently worked on first")]
+    Date,
+    #[value(help = "Alphabetically by title")]
+    Title,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ProjectArg {
+    #[arg(help = "Title of the project, or the name of its file")]
+    pub name: String,
+}
+
+pub struct ProjectsCommand;
+
+impl TerminalCommand for ProjectsCommand {
+    fn name(&self) -> &'static str {
+        "projects"
+    }
+
+    fn about(&self) -> &'static str {
+        "Curated list of projects that I've spent a lot of time on"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        ProjectsArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "projects",
+            "projects --tag rust",
+            "projects --sort date",
+            "projects --group prc",
+        ]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn execute(&self, matches: &ArgMatches, _: &mut TerminalState) -> Result<Effect, Error> {
+        let args = ProjectsArg::from_arg_matches(matches)?;
+        if let Some(name) = &args.group {
+            let groups: Vec<String> = PROJECT_GROUPS.iter().map(|group| group.name()).collect();
+            if !groups.contains(name) {
+                return Err(command_error(format!(
+                    "no group named '{name}', the groups are {}",
+                    groups.join(", ")
+                )));
+            }
+        }
+        Ok(Effect::None)
+    }
+
+    fn view(&self, matches: &ArgMatches, _: &TerminalState) -> Html {
+        view_with_args(matches, |args: ProjectsArg| {
+            if args.is_empty() {
+                return html! { <Projects /> };
+            }
+            let projects = args.projects();
+            if projects.is_empty() {
+                return html! { <p>{"No projects match"}</p> };
+            }
+            html! { <div class="comp-projects">
+                { for projects.into_iter().map(|project| html! { <Project project={project} /> }) }
+            </div> }
+        })
+    }
+
+    fn complete(&self, _: &[&str], arg: &Arg, _: &CompletionContext) -> Vec<Completion> {
+        match arg.get_id().as_str() {
+            "tag" => {
+                let mut tags: Vec<(&str, usize)> = Vec::new();
+                for tag in PROJECT_GROUPS
+                    .iter()
+                    .flat_map(|group| &group.projects)
+                    .flat_map(|project| &project.tags)
+                {
+                    match tags.iter_mut().find(|(t, _)| t == tag) {
+                        Some((_, count)) => *count += 1,
+                        None => tags.push((tag.as_str(), 1)),
+                    }
+                }
+                // the most used tags first
+                tags.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+                tags.into_iter()
+                    .map(|(tag, count)| {
+                        let plural = if count == 1 { "" } else { "s" };
+                        Completion::new(tag.to_string(), Some(format!("{count} project{plural}")))
+                    })
+                    .collect()
+            }
+            "group" => PROJECT_GROUPS
+                .iter()
+                .map(|group| Completion::new(group.name(), Some(group.title.clone())))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+pub struct ProjectCommand;
+
+impl TerminalCommand for ProjectCommand {
+    fn name(&self) -> &'static str {
+        "project"
+    }
+
+    fn about(&self) -> &'static str {
+        "Details of a single project, with its links and images"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        ProjectArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["project prc-rs", "project \"This Portfolio!\""]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn execute(&self, matches: &ArgMatches, _: &mut TerminalState) -> Result<Effect, Error> {
+        let ProjectArg { name } = ProjectArg::from_arg_matches(matches)?;
+        find_project(&name).ok_or_else(|| command_error(format!("no project named '{name}'")))?;
+        Ok(Effect::None)
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |ProjectArg { name }| match find_project(&name) {
+            Some((group, project)) => html! {
+                <ProjectDetail
+                    project={project}
+                    group={group.title.as_str()}
+                    onrun={state.onrun.clone()}
+                />
+            },
+            None => html! {},
+        })
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, _: &CompletionContext) -> Vec<Completion> {
+        PROJECT_GROUPS
+            .iter()
+            .flat_map(|group| &group.projects)
+            .map(|project| Completion::new(slug(&project.title), Some(project.summary.clone())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+fn titles(args: &[&str], groups: &[ProjectGroupInfo]) -> Vec<String> {
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: ProjectsArg,
+    }
+    let args = Cli::parse_from(std::iter::once("projects").chain(args.iter().copied())).args;
+    args.projects_in(groups)
+        .into_iter()
+        .map(|project| project.title.clone())
+        .collect()
+}
+
+#[test]
+fn test_filter_projects() {
+    let groups: crate::content::ProjectsFile = crate::content::parse(
+        "test.toml",
+        r#"
+        [[groups]]
+        title = "Web projects"
+        content_side = "left"
+        projects = [
+            { title = "beta", summary = "", tags = ["rust", "web"], started = 2019, ended = 2021, status = "archived" },
+            { title = "Alpha", summary = "", tags = ["rust"], started = 2022, status = "active" },
+            { title = "undated", summary = "", tags = ["Web"] },
+        ]
+
+        [[groups]]
+        title = "PRC projects"
+        content_side = "right"
+        projects = [
+            { title = "gamma", summary = "", tags = ["rust"], ended = 2023 },
+        ]
+        "#,
+    )
+    .unwrap();
+    let groups = &groups.groups;
+    assert_eq!(titles(&[], groups), ["beta", "Alpha", "undated", "gamma"]);
+    // every tag has to match, ignoring case
+    assert_eq!(titles(&["--tag", "web"], groups), ["beta", "undated"]);
+    assert_eq!(titles(&["-t", "rust", "-t", "WEB"], groups), ["beta"]);
+    // projects without a status never match one
+    assert_eq!(titles(&["--status", "active"], groups), ["Alpha"]);
+    assert_eq!(titles(&["--group", "prc"], groups), ["gamma"]);
+    assert_eq!(
+        titles(&["--sort", "date"], groups),
+        ["Alpha", "gamma", "beta", "undated"]
+    );
+    assert_eq!(
+        titles(&["--sort", "title", "--tag", "rust"], groups),
+        ["Alpha", "beta", "gamma"]
+    );
+}
@@ -0,0 +1,87 @@
+use clap::{Arg, ArgMatches, Args, Command, Error, FromArgMatches};
+use yew::{Callback, Html, html};
+
+use crate::cli::command_error;
+use crate::commands::{Effect, TerminalCommand, TerminalState, view_with_args};
+use crate::components::script_editor::ScriptEditor;
+use crate::utils::autocomplete::{Completion, CompletionContext};
+use crate::utils::startup_script::{STARTUP_FILE, StartupScript};
+
+#[derive(Debug, Clone, Args)]
+pub struct SourceArg {
+    #[arg(help = "The script to run, or none to write one")]
+    pub file: Option<String>,
+}
+
+pub struct SourceCommand;
+
+impl TerminalCommand for SourceCommand {
+    fn name(&self) -> &'static str {
+        "source"
+    }
+
+    fn about(&self) -> &'static str {
+        "Runs a script of commands, one on each line"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        SourceArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["source", "source .portfoliorc"]
+    }
+
+    fn execute(&self, matches: &ArgMatches, _: &mut TerminalState) -> Result<Effect, Error> {
+        match SourceArg::from_arg_matches(matches)?.file {
+            Some(file) if file == STARTUP_FILE => {
+                Ok(Effect::RunScript(StartupScript::new().script().to_string()))
+            }
+            Some(file) => Err(command_error(format!("no such file '{file}'"))),
+            None => Ok(Effect::None),
+        }
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |SourceArg { file }| match file {
+            // the output of the script follows this
+            Some(_) => html! {},
+            None => html! { <ScriptEditor onrun={state.onscript.clone()} /> },
+        })
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, _: &CompletionContext) -> Vec<Completion> {
+        vec![Completion::new(
+            STARTUP_FILE.to_string(),
+            Some("the startup script".to_string()),
+        )]
+    }
+}
+
+pub struct RcCommand;
+
+impl TerminalCommand for RcCommand {
+    fn name(&self) -> &'static str {
+        "rc"
+    }
+
+    fn about(&self) -> &'static str {
+        "Edits .portfoliorc, the script that runs when the terminal opens"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["rc"]
+    }
+
+    fn view(&self, _: &ArgMatches, state: &TerminalState) -> Html {
+        html! { <>
+            <h2>{STARTUP_FILE}</h2>
+            <p>{"These commands run each time the terminal opens. Save an empty script to turn it off."}</p>
+            <ScriptEditor
+                initial={StartupScript::new().script().to_string()}
+                onrun={state.onscript.clone()}
+                onsave={Callback::from(|script: String| StartupScript::new().save(&script))}
+            />
+        </> }
+    }
+}
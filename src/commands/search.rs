@@ -0,0 +1,73 @@
+use clap::{ArgMatches, Args, Command};
+use yew::{Html, MouseEvent, html};
+
+use crate::commands::{TerminalCommand, TerminalState, view_with_args};
+use crate::utils::search::{Highlighted, INDEX};
+
+#[derive(Debug, Clone, Args)]
+pub struct SearchArg {
+    #[arg(
+        required = true,
+        help = "Words to find, which all have to match. Quote several words to find them as a \
+                phrase, and end a word with * to match words starting with it"
+    )]
+    pub query: Vec<String>,
+}
+
+/// Shows text with the parts that matched marked
+fn view_highlighted(highlighted: &Highlighted) -> Html {
+    let text = &highlighted.text;
+    let mut parts = Vec::new();
+    let mut last = 0;
+    for range in &highlighted.matches {
+        parts.push(html! { &text[last..range.start] });
+        parts.push(html! { <mark>{&text[range.clone()]}</mark> });
+        last = range.end;
+    }
+    parts.push(html! { &text[last..] });
+    parts.into_iter().collect()
+}
+
+pub struct SearchCommand;
+
+impl TerminalCommand for SearchCommand {
+    fn name(&self) -> &'static str {
+        "search"
+    }
+
+    fn about(&self) -> &'static str {
+        "Searches through my projects, skills and everything else here"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        SearchArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["search rust", "search \"param files\"", "search web rea*"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn view(&self, matches: &ArgMatches, state: &TerminalState) -> Html {
+        view_with_args(matches, |SearchArg { query }| {
+            let hits = INDEX.search(&query);
+            if hits.is_empty() {
+                return html! { <p>{format!("No results for '{}'", query.join(" "))}</p> };
+            }
+            html! { <ul class="search-results">
+                { for hits.iter().map(|hit| {
+                    let command = hit.document.command.clone();
+                    let onclick = state.onrun.reform(move |_: MouseEvent| command.clone());
+                    html! { <li>
+                        <span class="cmd" onclick={onclick}>{view_highlighted(&hit.title)}</span>
+                        <span class="search-command">{&hit.document.command}</span>
+                        <p class="search-snippet">{view_highlighted(&hit.snippet)}</p>
+                    </li> }
+                }) }
+            </ul> }
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use clap::ArgMatches;
+use yew::{Html, html};
+
+use crate::commands::{TerminalCommand, TerminalState};
+use crate::content::SKILLS;
+
+pub struct SkillsCommand;
+
+impl TerminalCommand for SkillsCommand {
+    fn name(&self) -> &'static str {
+        "skills"
+    }
+
+    fn about(&self) -> &'static str {
+        "Curated subjects or frameworks I have experience with"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["skills"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn view(&self, _: &ArgMatches, _: &TerminalState) -> Html {
+        html! {
+            for SKILLS.iter().map(|skill| {
+                html! { <>
+                    <h2>{&skill.category}</h2>
+                    <ul>{ for skill.bullets.iter().map(|b| html! {<li>{b}</li>}) }</ul>
+                </> }
+            })
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use clap::{Arg, ArgMatches, Args, Command, Error, FromArgMatches};
+use yew::{Html, html};
+
+use crate::cli::command_error;
+use crate::commands::{Effect, TerminalCommand, TerminalState};
+use crate::utils::autocomplete::{Completion, CompletionContext};
+use crate::utils::variables::{READ_ONLY, parse_assignment};
+
+#[derive(Debug, Clone, Args)]
+pub struct SetArg {
+    #[arg(help = "An assignment in the form NAME=value")]
+    pub assignment: String,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct UnsetArg {
+    #[arg(help = "Name of the variable to remove")]
+    pub name: String,
+}
+
+pub struct SetCommand;
+
+impl TerminalCommand for SetCommand {
+    fn name(&self) -> &'static str {
+        "set"
+    }
+
+    fn about(&self) -> &'static str {
+        "Sets a variable, which can be used in commands as $NAME"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        SetArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "set NAME=world",
+            "set GREETING='hello there'",
+            "set HISTCONTROL=ignoreboth",
+        ]
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        let SetArg { assignment } = SetArg::from_arg_matches(matches)?;
+        let (name, value) = parse_assignment(&assignment).map_err(command_error)?;
+        state
+            .variables
+            .set(name, value.to_string())
+            .map_err(command_error)?;
+        Ok(Effect::None)
+    }
+
+    fn view(&self, _: &ArgMatches, _: &TerminalState) -> Html {
+        html! {}
+    }
+}
+
+pub struct UnsetCommand;
+
+impl TerminalCommand for UnsetCommand {
+    fn name(&self) -> &'static str {
+        "unset"
+    }
+
+    fn about(&self) -> &'static str {
+        "Removes a variable"
+    }
+
+    fn args(&self, cmd: Command) -> Command {
+        UnsetArg::augment_args(cmd)
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["unset NAME"]
+    }
+
+    fn execute(&self, matches: &ArgMatches, state: &mut TerminalState) -> Result<Effect, Error> {
+        let UnsetArg { name } = UnsetArg::from_arg_matches(matches)?;
+        state
+            .variables
+            .remove(&name)
+            .map_err(command_error)?
+            .ok_or_else(|| command_error(format!("variable '{name}' is not set")))?;
+        Ok(Effect::None)
+    }
+
+    fn view(&self, _: &ArgMatches, _: &TerminalState) -> Html {
+        html! {}
+    }
+
+    fn complete(&self, _: &[&str], _: &Arg, context: &CompletionContext) -> Vec<Completion> {
+        context
+            .variables
+            .vars()
+            .map(|(name, value)| Completion::new(name.to_string(), Some(value.to_string())))
+            .collect()
+    }
+}
+
+pub struct EnvCommand;
+
+impl TerminalCommand for EnvCommand {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    fn about(&self) -> &'static str {
+        "Lists all variables and their values"
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["env"]
+    }
+
+    fn read_only(&self, _: &ArgMatches) -> bool {
+        true
+    }
+
+    fn view(&self, _: &ArgMatches, state: &TerminalState) -> Html {
+        html! { <ul class="variable-list">
+            { for READ_ONLY.iter().map(|(name, description)| html! {
+                <li>
+                    <span class="emph">{name}</span>
+                    {format!("={}", state.variable(name).unwrap_or_default())}
+                    <span class="ital">{format!(" ({description})")}</span>
+                </li>
+            }) }
+            { for state.variables.vars().map(|(name, value)| html! {
+                <li><span class="emph">{name}</span>{format!("='{value}'")}</li>
+            }) }
+        </ul> }
+    }
+}
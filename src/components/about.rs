@@ -3,7 +3,31 @@ use yew::prelude::*;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct About;
 
-const DOG: &'static str = include_str!("dog.txt");
+const DOG: &str = include_str!("dog.txt");
+
+/// Paragraphs introducing myself, where text between `**` is emphasized
+pub const BIO: &[&str] = &[
+    "Hi! My name is **Benjamin Hall**, I'm a software engineer and full-stack web developer. I \
+     was born and raised in Northern CA, but I currently live in Harrisburg, PA.",
+    "Growing up, I wanted to be a mathematician. When I entered the professional context, I \
+     applied my knowledge of math and computer programming and became a **Full-Stack Web and \
+     Software Engineer.**",
+    "Professionally I write web apps, and am experienced with various frameworks and systems \
+     (**React, Ruby on Rails, TypeScript, etc**).",
+    "Although I can write in a number of frameworks and languages, I prefer to write in Rust. \
+     I'm frequently developing a combination of systems or web programming projects. For \
+     example, I've written more than half a dozen apps to help users mod games (in particular: \
+     Super Smash Bros. Ultimate).",
+    "I have an adorable, smart labradoodle named Lady 🐶",
+    "I have a fair number of hobbies! If I had to rank them, I would do so as follows:",
+];
+
+pub const HOBBIES: &[&str] = &[
+    "Piano / Classical music (15+ years)",
+    "Cycling (road & gravel)",
+    "Botany (2000+ observations on iNat)",
+    "Photography (I've sold some prints!)",
+];
 
 impl Component for About {
     type Message = ();
@@ -29,31 +53,21 @@ impl Component for About {
 
             <hr />
 
-            <p>
-                {"Hi! My name is "}<span class="emph">{"Benjamin Hall"}</span>
-                {", I'm a software engineer and full-stack web developer. I was born and raised in Northern CA, but I currently live in \
-                  Harrisburg, PA."}
-                <br/><br/>
-                {"Growing up, I wanted to be a mathematician. When I entered the professional context, I applied my knowledge of math and \
-                  computer programming and became a "}<span class="emph">{"Full-Stack Web and Software Engineer."}</span>
-                <br/><br/>
-                {"Professionally I write web apps, and am experienced with various frameworks and systems ("}
-                <span class="emph">{"React, Ruby on Rails, TypeScript, etc"}</span>{")."}
-                <br/><br/>
-                {"Although I can write in a number of frameworks and languages, I prefer to write in Rust. I'm frequently developing a \
-                  combination of systems or web programming projects. For example, I've written more than half a dozen apps to help users \
-                  mod games (in particular: Super Smash Bros. Ultimate)."}
-                <br/><br/>
-                {"I have an adorable, smart labradoodle named Lady 🐶"}
-                <br/><br/>
-                {"I have a fair number of hobbies! If I had to rank them, I would do so as follows:"}
-            </p>
+            { for BIO.iter().map(|paragraph| view_paragraph(paragraph)) }
             <ul>
-                <li>{"Piano / Classical music (15+ years)"}</li>
-                <li>{"Cycling (road & gravel)"}</li>
-                <li>{"Botany (2000+ observations on iNat)"}</li>
-                <li>{"Photography (I've sold some prints!)"}</li>
+                { for HOBBIES.iter().map(|hobby| html! { <li>{*hobby}</li> }) }
             </ul>
         </div> }
     }
 }
+
+/// Shows a paragraph of the bio, emphasizing the text between `**`
+fn view_paragraph(text: &str) -> Html {
+    html! { <p>
+        { for text.split("**").enumerate().map(|(i, part)| if i % 2 == 1 {
+            html! { <span class="emph">{part.to_string()}</span> }
+        } else {
+            html! { part.to_string() }
+        }) }
+    </p> }
+}
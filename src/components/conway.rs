@@ -1,3 +1,4 @@
+use crate::commands::Interrupts;
 use crate::utils::game::*;
 use std::str::FromStr;
 
@@ -23,6 +24,20 @@ pub struct Conway {
     form_width: String,
     form_height: String,
     canvas: NodeRef,
+    /// Pauses the simulation whenever Ctrl-C is pressed
+    _interrupts: Option<ContextHandle<Interrupts>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ConwayProps {
+    #[prop_or(DEFAULT_PRESET)]
+    pub preset: GamePreset,
+    /// Overrides the preset's width
+    #[prop_or_default]
+    pub width: Option<usize>,
+    /// Overrides the preset's height
+    #[prop_or_default]
+    pub height: Option<usize>,
 }
 
 pub enum ConwayMessage {
@@ -39,9 +54,17 @@ pub enum ConwayMessage {
 
 impl Component for Conway {
     type Message = ConwayMessage;
-    type Properties = ();
+    type Properties = ConwayProps;
 
-    fn create(_: &Context<Self>) -> Self {
+    fn create(context: &Context<Self>) -> Self {
+        let interrupts = context
+            .link()
+            .context(
+                context
+                    .link()
+                    .callback(|_: Interrupts| ConwayMessage::Pause),
+            )
+            .map(|(_, handle)| handle);
         Conway {
             job: None,
             game: Game::new(DEFAULT_WIDTH, DEFAULT_HEIGHT, false),
@@ -50,6 +73,7 @@ impl Component for Conway {
             form_width: String::from(""),
             form_height: String::from(""),
             canvas: NodeRef::default(),
+            _interrupts: interrupts,
         }
     }
 
@@ -109,16 +133,30 @@ impl Component for Conway {
                     coord[0] = self.width - 1;
                 }
                 // TODO: this iteration (trying to avoid allocation) is ugly
-                self.game.invert([coord].iter().map(|a| *a));
+                self.game.invert([coord].iter().copied());
                 self.draw();
                 false
             }
         }
     }
 
-    fn rendered(&mut self, _: &Context<Self>, first_render: bool) {
+    fn rendered(&mut self, context: &Context<Self>, first_render: bool) {
         if first_render {
-            self.set_preset(DEFAULT_PRESET);
+            let props = context.props();
+            self.set_preset(props.preset.clone());
+            if props.width.is_some() || props.height.is_some() {
+                let width = props.width.unwrap_or(self.width);
+                let height = props.height.unwrap_or(self.height);
+                self.set_dimensions(width, height);
+                self.game.set_on(
+                    props
+                        .preset
+                        .cells
+                        .iter()
+                        .copied()
+                        .filter(|[y, x]| *y < height && *x < width),
+                );
+            }
             self.draw();
         }
     }
@@ -255,7 +293,7 @@ impl Conway {
         context.set_fill_style_str(DEFAULT_ALIVE_COLOR);
         for row in 0..height {
             for col in 0..width {
-                if self.game.front()[[row as usize, col as usize]] != 0 {
+                if self.game.front()[[row, col]] != 0 {
                     context.fill_rect(
                         (col * size) as f64,
                         (row * size) as f64,
@@ -269,7 +307,7 @@ impl Conway {
 
     fn set_preset(&mut self, preset: GamePreset) {
         self.set_dimensions(preset.width, preset.height);
-        self.game.set_on(preset.cells.iter().map(|c| *c));
+        self.game.set_on(preset.cells.iter().copied());
     }
 
     fn set_dimensions(&mut self, width: usize, height: usize) {
@@ -10,6 +10,14 @@ pub struct Differ {
     b: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct DifferProps {
+    #[prop_or_default]
+    pub a: String,
+    #[prop_or_default]
+    pub b: String,
+}
+
 #[derive(Debug, Clone)]
 pub enum DifferMessage {
     SetA(String),
@@ -18,12 +26,13 @@ pub enum DifferMessage {
 
 impl Component for Differ {
     type Message = DifferMessage;
-    type Properties = ();
+    type Properties = DifferProps;
 
-    fn create(_: &Context<Self>) -> Self {
+    fn create(context: &Context<Self>) -> Self {
+        let props = context.props();
         Differ {
-            a: String::new(),
-            b: String::new(),
+            a: props.a.clone(),
+            b: props.b.clone(),
         }
     }
 
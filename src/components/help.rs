@@ -0,0 +1,207 @@
+use clap::{Arg, Command};
+use yew::prelude::*;
+
+use crate::cli::{examples, find_command, root_command};
+use crate::commands::view_runnable;
+
+/// A help page for a command, or for the whole terminal, built from the command's definition
+pub struct Help;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct HelpProps {
+    /// Names of the subcommands leading to the command to show, which is empty for the list of
+    /// all commands
+    #[prop_or_default]
+    pub path: Vec<String>,
+    /// Runs a line, when one of the examples or commands is clicked
+    pub onrun: Callback<String>,
+}
+
+impl Component for Help {
+    type Message = ();
+    type Properties = HelpProps;
+
+    fn create(_: &Context<Self>) -> Self {
+        Help
+    }
+
+    fn view(&self, context: &Context<Self>) -> Html {
+        let props = context.props();
+        let root = root_command();
+
+        let mut cmd = &root;
+        let mut names: Vec<&str> = Vec::new();
+        for name in &props.path {
+            match find_command(cmd, std::slice::from_ref(name)) {
+                Some(sub) => {
+                    names.push(sub.get_name());
+                    cmd = sub;
+                }
+                None => {
+                    return html! {
+                        <p>{format!("No such command '{}'", props.path.join(" "))}</p>
+                    };
+                }
+            }
+        }
+
+        let positionals: Vec<&Arg> = cmd.get_positionals().collect();
+        let options: Vec<&Arg> = cmd
+            .get_arguments()
+            .filter(|a| !a.is_positional() && !a.is_hide_set())
+            .collect();
+
+        html! { <div class="help-page">
+            <h2>{if names.is_empty() { "Commands".to_string() } else { names.join(" ") }}</h2>
+            {match cmd.get_about() {
+                Some(about) if !names.is_empty() => html! { <p>{about.to_string()}</p> },
+                _ => html! {},
+            }}
+            <p class="help-usage">
+                <span class="emph">{"usage: "}</span>{usage(&names, cmd)}
+            </p>
+            {Self::view_commands(&names, cmd, &props.onrun)}
+            {Self::view_args("Arguments", &positionals)}
+            {Self::view_args("Options", &options)}
+            {Self::view_examples(&names, &props.onrun)}
+        </div> }
+    }
+}
+
+impl Help {
+    fn view_commands(names: &[&str], cmd: &Command, onrun: &Callback<String>) -> Html {
+        if !cmd.has_subcommands() {
+            return html! {};
+        }
+        html! { <>
+            <h3>{"Commands"}</h3>
+            <table class="help-table">
+                { for cmd.get_subcommands().map(|sub| {
+                    let line = ["help"]
+                        .iter()
+                        .chain(names)
+                        .chain([&sub.get_name()])
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    html! { <tr>
+                        <td>{view_runnable(sub.get_name(), line, onrun)}</td>
+                        <td>{sub.get_about().map(ToString::to_string).unwrap_or_default()}</td>
+                    </tr> }
+                }) }
+            </table>
+        </> }
+    }
+
+    fn view_args(title: &str, args: &[&Arg]) -> Html {
+        if args.is_empty() {
+            return html! {};
+        }
+        html! { <>
+            <h3>{title}</h3>
+            <table class="help-table">
+                { for args.iter().map(|arg| html! { <tr>
+                    <td class="emph">{arg_label(arg)}</td>
+                    <td>
+                        {arg.get_help().map(ToString::to_string).unwrap_or_default()}
+                        {for arg_details(arg).into_iter().map(|detail| html! {
+                            <span class="help-detail ital">{detail}</span>
+                        })}
+                    </td>
+                </tr> }) }
+            </table>
+        </> }
+    }
+
+    fn view_examples(names: &[&str], onrun: &Callback<String>) -> Html {
+        let lines = examples(names);
+        if lines.is_empty() {
+            return html! {};
+        }
+        html! { <>
+            <h3>{"Examples"}</h3>
+            <ul class="help-examples">
+                { for lines.iter().map(|line| html! {
+                    <li>{view_runnable(line, line.to_string(), onrun)}</li>
+                }) }
+            </ul>
+        </> }
+    }
+}
+
+/// Writes how a command is used, like `history index <NUM>`
+fn usage(names: &[&str], cmd: &Command) -> String {
+    let mut parts: Vec<String> = names.iter().map(ToString::to_string).collect();
+    if cmd
+        .get_arguments()
+        .any(|a| !a.is_positional() && !a.is_hide_set())
+    {
+        parts.push("[OPTIONS]".to_string());
+    }
+    if cmd.has_subcommands() {
+        parts.push("<COMMAND>".to_string());
+    }
+    parts.extend(cmd.get_positionals().map(|arg| {
+        let name = value_name(arg);
+        let repeated = if arg.get_num_args().is_some_and(|n| n.max_values() > 1) {
+            "..."
+        } else {
+            ""
+        };
+        if arg.is_required_set() {
+            format!("<{name}>{repeated}")
+        } else {
+            format!("[{name}]{repeated}")
+        }
+    }));
+    parts.join(" ")
+}
+
+/// Writes an argument the way it's typed, like `-p, --preset <PRESET>` or `<NUM>`
+fn arg_label(arg: &Arg) -> String {
+    if arg.is_positional() {
+        return format!("<{}>", value_name(arg));
+    }
+    let mut names = Vec::new();
+    if let Some(short) = arg.get_short() {
+        names.push(format!("-{short}"));
+    }
+    if let Some(long) = arg.get_long() {
+        names.push(format!("--{long}"));
+    }
+    let mut label = names.join(", ");
+    if arg.get_action().takes_values() {
+        label.push_str(&format!(" <{}>", value_name(arg)));
+    }
+    label
+}
+
+fn value_name(arg: &Arg) -> String {
+    arg.get_value_names()
+        .and_then(|names| names.first())
+        .map(ToString::to_string)
+        .unwrap_or_else(|| arg.get_id().as_str().to_uppercase())
+}
+
+/// Lists an argument's default and the values it accepts
+fn arg_details(arg: &Arg) -> Vec<String> {
+    let mut details = Vec::new();
+    let defaults: Vec<String> = arg
+        .get_default_values()
+        .iter()
+        .map(|value| value.to_string_lossy().into_owned())
+        .collect();
+    if !defaults.is_empty() {
+        details.push(format!("default: {}", defaults.join(", ")));
+    }
+    let values: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| value.get_name().to_string())
+        .collect();
+    if !values.is_empty() && arg.get_action().takes_values() {
+        details.push(format!("values: {}", values.join(", ")));
+    }
+    details
+}
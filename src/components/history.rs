@@ -1,90 +1,57 @@
 use yew::prelude::*;
-// use yew::services::StorageService;
+
+use crate::commands::view_runnable;
+use crate::utils::history_store::{HistoryEntry, relative_time};
 
 #[derive(Debug, Clone)]
-pub enum History {
-    All(Vec<String>),
-    One(String),
-    OOB,
-}
+pub struct History;
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct HistoryProps {
-    pub items: Vec<String>,
-    #[prop_or(None)]
-    pub index: Option<usize>,
+    /// The entries to show, along with their indices in the history
+    pub items: Vec<(usize, HistoryEntry)>,
+    /// Runs an entry's line again, when it's clicked
+    pub onrun: Callback<String>,
 }
 
 impl Component for History {
     type Message = ();
     type Properties = HistoryProps;
 
-    fn create(context: &Context<Self>) -> Self {
-        let props = context.props();
-        if let Some(i) = props.index {
-            if i >= props.items.len() {
-                History::OOB
-            } else {
-                History::One(props.items[i].clone())
-            }
-        } else {
-            History::All(props.items.clone())
-        }
-    }
-
-    fn update(&mut self, _: &Context<Self>, _: Self::Message) -> bool {
-        true
-    }
-
-    fn changed(&mut self, _: &Context<Self>, props: &Self::Properties) -> bool {
-        *self = if let Some(i) = props.index {
-            if i >= props.items.len() {
-                History::OOB
-            } else {
-                History::One(props.items[i].clone())
-            }
-        } else {
-            History::All(props.items.clone())
-        };
-        true
+    fn create(_: &Context<Self>) -> Self {
+        History
     }
 
-    fn view(&self, _: &Context<Self>) -> Html {
-        match self {
-            History::All(h) => {
-                html! { <>
-                    <h2>{"Command History"}</h2>
-                    <p>{format!("count: {}", h.len())}</p>
-                    <ul>{
-                        h.iter()
-                            .enumerate()
-                            .map(|(i, v)| History::view_item(Some(i), v))
-                            .collect::<Html>()
-                    }</ul>
-                </> }
-            }
-            History::One(h) => {
-                html! { <>
-                    <h2>{"Command History"}</h2>
-                    <ul>{History::view_item(None, &h)}</ul>
-                </> }
-            }
-            History::OOB => {
-                html! { <p>{"Index was out of bounds"}</p> }
-            }
-        }
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let HistoryProps { items, onrun } = ctx.props();
+        let now = js_sys::Date::now();
+        html! { <>
+            <h2>{"Command History"}</h2>
+            <p>{format!("count: {}", items.len())}</p>
+            <ul>{
+                items
+                    .iter()
+                    .map(|(i, item)| History::view_item(*i, item, now, onrun))
+                    .collect::<Html>()
+            }</ul>
+        </> }
     }
 }
 
 impl History {
-    fn view_item(index: Option<usize>, item: &str) -> Html {
-        if let Some(i) = index {
-            html! { <li>
-                {format!("{} - ", i)}
-                {item}
-            </li> }
-        } else {
-            html!(item)
+    fn view_item(index: usize, item: &HistoryEntry, now: f64, onrun: &Callback<String>) -> Html {
+        let mut notes: Vec<String> = item
+            .time
+            .map(|time| relative_time(time, now))
+            .into_iter()
+            .collect();
+        if !item.ok {
+            notes.push("failed".to_string());
         }
+        html! { <li>
+            {format!("{} - ", index)}
+            {view_runnable(&item.input, item.input.clone(), onrun)}
+            <span class="history-time ital">{notes.join(", ")}</span>
+        </li> }
     }
 }
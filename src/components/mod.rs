@@ -1,5 +1,7 @@
 pub mod about;
 pub mod conway;
 pub mod differ;
+pub mod help;
 pub mod history;
 pub mod projects;
+pub mod script_editor;
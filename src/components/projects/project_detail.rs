@@ -0,0 +1,65 @@
+use yew::prelude::*;
+
+use crate::commands::view_runnable;
+use crate::content::ProjectInfo;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ProjectDetailProps {
+    pub project: &'static ProjectInfo,
+    /// The title of the group the project is listed in
+    pub group: &'static str,
+    /// Runs a line, when the link back to the list of projects is clicked
+    pub onrun: Callback<String>,
+}
+
+/// Everything about a single project, shown on its own
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectDetail;
+
+impl Component for ProjectDetail {
+    type Message = ();
+
+    type Properties = ProjectDetailProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let ProjectDetailProps {
+            project,
+            group,
+            onrun,
+        } = ctx.props();
+        html! {<div class="comp-project-detail">
+            <h1>{&project.title}</h1>
+            <p class="ital">
+                {"Listed under "}{view_runnable(group, "projects".to_string(), onrun)}
+            </p>
+            <p>{&project.summary}</p>
+            {if !project.bullets.is_empty() {
+                html! {<ul>{ for project.bullets.iter().map(|b| html! {<li>{b}</li>}) }</ul>}
+            } else {
+                html! {}
+            }}
+            <table class="help-table">
+                {for project.details().into_iter().map(|(label, value)| html! {<tr>
+                    <td class="emph">{label}</td>
+                    <td>{value}</td>
+                </tr>})}
+                <tr><td class="emph">{"Tags"}</td><td>{project.tags.join(", ")}</td></tr>
+                {for project.deployment.iter().map(|url| html! {<tr>
+                    <td class="emph">{"Deployment"}</td>
+                    <td><a href={url.clone()}>{url}</a></td>
+                </tr>})}
+                {for project.sources.iter().map(|(label, url)| html! {<tr>
+                    <td class="emph">{label}</td>
+                    <td><a href={url.clone()}>{url}</a></td>
+                </tr>})}
+            </table>
+            {for project.images.iter().map(|image| html! {
+                <img class="project-detail-image" src={image.src.clone()} alt={image.alt.clone()} />
+            })}
+        </div>}
+    }
+}
@@ -1,27 +1,14 @@
 use yew::prelude::*;
 
-pub use project::{Project, ProjectInfo};
+use crate::content::{ProjectGroupInfo, Side};
 
-mod project;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Side {
-    Left,
-    Right,
-}
+pub use project::Project;
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct ImageProps {
-    pub src: &'static str,
-    pub alt: &'static str,
-}
+mod project;
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct ProjectGroupProps {
-    pub title: &'static str,
-    pub projects: Vec<ProjectInfo>,
-    pub content_side: Side,
-    pub content: Vec<ImageProps>,
+    pub group: &'static ProjectGroupInfo,
 }
 
 #[derive(Debug, Clone)]
@@ -37,28 +24,28 @@ impl Component for ProjectGroup {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let props = ctx.props();
+        let group = ctx.props().group;
 
-        let group_data_class = match props.content_side {
+        let group_data_class = match group.content_side {
             Side::Left => "project-group-data row",
             Side::Right => "project-group-data row-reverse",
         };
 
         html! {<div class="comp-project-group">
-            <h1>{props.title}</h1>
+            <h1>{&group.title}</h1>
             <div class={group_data_class}>
-                {if !props.content.is_empty() {
+                {if group.projects.iter().any(|project| !project.images.is_empty()) {
                     html! {<div class="project-group-content">
-                        {for props.content.iter().map(|image_props| html!{
-                            <img src={image_props.src} alt={image_props.alt} />
+                        {for group.projects.iter().flat_map(|project| &project.images).map(|image_props| html!{
+                            <img src={image_props.src.clone()} alt={image_props.alt.clone()} />
                         })}
                     </div>}
                 } else {
                     html!{}
                 }}
                 <div class="project-group-details">
-                    {for props.projects.iter().map(|project| html! {
-                        <Project ..project.clone()/>
+                    {for group.projects.iter().map(|project| html! {
+                        <Project project={project} />
                     })}
                 </div>
             </div>
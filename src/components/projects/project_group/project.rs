@@ -1,12 +1,10 @@
 use yew::prelude::*;
 
+use crate::content::ProjectInfo;
+
 #[derive(Debug, Clone, PartialEq, Properties)]
-pub struct ProjectInfo {
-    pub title: &'static str,
-    pub deployment: Option<&'static str>, // hyperlinks on the title
-    pub summary: &'static str,
-    pub bullets: Vec<&'static str>,
-    pub sources: Vec<(&'static str, &'static str)>,
+pub struct ProjectProps {
+    pub project: &'static ProjectInfo,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -15,20 +13,32 @@ pub struct Project;
 impl Component for Project {
     type Message = ();
 
-    type Properties = ProjectInfo;
+    type Properties = ProjectProps;
 
     fn create(_ctx: &Context<Self>) -> Self {
         Self
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let props = ctx.props();
+        let props = ctx.props().project;
+        let details: Vec<String> = props
+            .details()
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect();
         html! {<div class="comp-project">
-            <h2>{ match props.deployment {
-                None => html! {props.title},
-                Some(dep) => html! {<a href={dep}>{props.title}</a>},
+            <h2>{ match &props.deployment {
+                None => html! {&props.title},
+                Some(dep) => html! {<a href={dep.clone()}>{&props.title}</a>},
             }}</h2>
-            <p>{props.summary}</p>
+            {
+                if !details.is_empty() {
+                    html! {<p class="project-meta ital">{details.join(" · ")}</p>}
+                } else {
+                    html! {}
+                }
+            }
+            <p>{&props.summary}</p>
             {
                 if !props.bullets.is_empty() {
                     html! {<ul>{ for props.bullets.iter().map(|b| html! {<li>{b}</li>}) }</ul>}
@@ -42,9 +52,12 @@ impl Component for Project {
                     .enumerate()
                     .map(|(i, (t, url))| html! { <>
                         {if i > 0 { " / " } else { "" }}
-                        <a href={*url}>{t}</a>
+                        <a href={url.clone()}>{t}</a>
                     </>})
             }
+            <div class="project-tags">
+                {for props.tags.iter().map(|tag| html! {<span class="project-tag">{tag}</span>})}
+            </div>
         </div>}
     }
 }
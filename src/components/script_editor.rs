@@ -0,0 +1,92 @@
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+/// A textarea for writing a script with one command on each line, which can be run or saved
+#[derive(Debug, Clone)]
+pub struct ScriptEditor {
+    script: String,
+    saved: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ScriptEditorProps {
+    #[prop_or_default]
+    pub initial: String,
+    pub onrun: Callback<String>,
+    /// Shows a save button as well, when the script is kept somewhere
+    #[prop_or_default]
+    pub onsave: Option<Callback<String>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ScriptEditorMessage {
+    Set(String),
+    Run,
+    Save,
+}
+
+impl Component for ScriptEditor {
+    type Message = ScriptEditorMessage;
+    type Properties = ScriptEditorProps;
+
+    fn create(context: &Context<Self>) -> Self {
+        ScriptEditor {
+            script: context.props().initial.clone(),
+            saved: false,
+        }
+    }
+
+    fn update(&mut self, context: &Context<Self>, msg: Self::Message) -> bool {
+        let props = context.props();
+        match msg {
+            ScriptEditorMessage::Set(script) => {
+                self.script = script;
+                self.saved = false;
+            }
+            ScriptEditorMessage::Run => props.onrun.emit(self.script.clone()),
+            ScriptEditorMessage::Save => {
+                if let Some(onsave) = &props.onsave {
+                    onsave.emit(self.script.clone());
+                    self.saved = true;
+                }
+            }
+        }
+        true
+    }
+
+    // the script being written is kept, even if the terminal renders again
+    fn changed(&mut self, _: &Context<Self>, _: &Self::Properties) -> bool {
+        false
+    }
+
+    fn view(&self, context: &Context<Self>) -> Html {
+        let link = context.link();
+        let oninput = link.callback(|e: InputEvent| {
+            ScriptEditorMessage::Set(
+                e.target_dyn_into::<HtmlTextAreaElement>()
+                    .map(|elem| elem.value())
+                    .unwrap_or_default(),
+            )
+        });
+
+        html! { <div class="script-editor">
+            <textarea
+                rows="8"
+                spellcheck="false"
+                placeholder={"# one command on each line\nalias p=projects"}
+                value={self.script.clone()}
+                oninput={oninput}
+            />
+            <div class="script-buttons">
+                <button onclick={link.callback(|_| ScriptEditorMessage::Run)}>{"Run"}</button>
+                {match context.props().onsave {
+                    Some(_) => html! {
+                        <button onclick={link.callback(|_| ScriptEditorMessage::Save)}>{"Save"}</button>
+                    },
+                    None => html! {},
+                }}
+                {if self.saved { html! { <span class="ital">{"Saved"}</span> } } else { html! {} }}
+            </div>
+        </div> }
+    }
+}
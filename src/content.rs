@@ -0,0 +1,191 @@
+//! The portfolio's content, loaded from the TOML files in `content/`. This module only depends on
+//! serde and toml, so the build script can include it to check the files before the app is
+//! built, reporting errors with the file and line they're on.
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+pub const PROJECTS_FILE: &str = "content/projects.toml";
+pub const SKILLS_FILE: &str = "content/skills.toml";
+
+pub static PROJECT_GROUPS: LazyLock<Vec<ProjectGroupInfo>> = LazyLock::new(|| {
+    let text = include_str!("../content/projects.toml");
+    load::<ProjectsFile>(PROJECTS_FILE, text).groups
+});
+
+pub static SKILLS: LazyLock<Vec<Skill>> = LazyLock::new(|| {
+    let text = include_str!("../content/skills.toml");
+    load::<SkillsFile>(SKILLS_FILE, text).skills
+});
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectsFile {
+    pub groups: Vec<ProjectGroupInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillsFile {
+    pub skills: Vec<Skill>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectGroupInfo {
+    pub title: String,
+    /// Which side the images of the group's projects are shown on
+    pub content_side: Side,
+    pub projects: Vec<ProjectInfo>,
+}
+
+impl ProjectGroupInfo {
+    /// A short name for the group, like `prc` for `PRC projects`
+    pub fn name(&self) -> String {
+        slug(self.title.trim_end_matches(" projects"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImageProps {
+    pub src: String,
+    pub alt: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectStatus {
+    /// Still being worked on
+    Active,
+    /// Finished, or no longer worked on
+    Archived,
+}
+
+impl fmt::Display for ProjectStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProjectStatus::Active => "active",
+            ProjectStatus::Archived => "archived",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectInfo {
+    pub title: String,
+    /// A link to where the project can be used, which the title links to
+    pub deployment: Option<String>,
+    pub summary: String,
+    #[serde(default)]
+    pub bullets: Vec<String>,
+    /// Links to the project's code, as a label and a URL
+    #[serde(default)]
+    pub sources: Vec<(String, String)>,
+    #[serde(default)]
+    pub images: Vec<ImageProps>,
+    /// Languages and tools the project is built with, in lowercase
+    pub tags: Vec<String>,
+    /// The year work on the project started, if it's known
+    pub started: Option<u16>,
+    /// The year work on the project stopped, which is left out while it's ongoing
+    pub ended: Option<u16>,
+    pub status: Option<ProjectStatus>,
+    /// The part I had in the project
+    pub role: Option<String>,
+}
+
+impl ProjectInfo {
+    /// Writes the years the project was worked on, like `2018 – 2020` or `2022 – present`, if
+    /// they're known
+    pub fn dates(&self) -> Option<String> {
+        match (self.started, self.ended) {
+            (Some(started), Some(ended)) if ended == started => Some(ended.to_string()),
+            (Some(started), Some(ended)) => Some(format!("{started} – {ended}")),
+            (Some(started), None) => Some(format!("{started} – present")),
+            (None, Some(ended)) => Some(ended.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// The role, status and dates of the project which are known, each with a label
+    pub fn details(&self) -> Vec<(&'static str, String)> {
+        [
+            ("Role", self.role.clone()),
+            ("Status", self.status.map(|status| status.to_string())),
+            ("Dates", self.dates()),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| Some((label, value?)))
+        .collect()
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Skill {
+    pub category: String,
+    pub bullets: Vec<String>,
+}
+
+/// Turns a title into a file name, like `This Portfolio!` into `this-portfolio`
+pub fn slug(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Reads a content file, describing any error with the file, line and column it's at
+pub fn parse<T: DeserializeOwned>(file: &str, text: &str) -> Result<T, String> {
+    toml::from_str(text).map_err(|err| {
+        let (line, column) = match err.span() {
+            Some(span) => {
+                let before = &text[..span.start];
+                let line = before.matches('\n').count() + 1;
+                let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+                (line, column)
+            }
+            None => (1, 1),
+        };
+        format!("{file}:{line}:{column}: {}", err.message().trim_end())
+    })
+}
+
+/// Reads a content file which is known to be valid, since the build checks it
+fn load<T: DeserializeOwned>(file: &str, text: &str) -> T {
+    parse(file, text).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[test]
+fn test_content_errors() {
+    let missing = "[[skills]]\ncategory = \"Languages\"\n";
+    assert_eq!(
+        parse::<SkillsFile>(SKILLS_FILE, missing).unwrap_err(),
+        "content/skills.toml:1:1: missing field `bullets`"
+    );
+    let status = "[[groups]]\ntitle = \"Web\"\ncontent_side = \"up\"\nprojects = []\n";
+    let err = parse::<ProjectsFile>(PROJECTS_FILE, status).unwrap_err();
+    assert!(
+        err.starts_with("content/projects.toml:3:16: unknown variant `up`"),
+        "{err}"
+    );
+    assert!(!PROJECT_GROUPS.is_empty() && !SKILLS.is_empty());
+}
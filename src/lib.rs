@@ -1,37 +1,84 @@
 #![recursion_limit = "1024"]
 
 mod cli;
+mod commands;
 mod components;
-mod skills;
+mod content;
 mod utils;
 
-use clap::{Error, Parser};
-use components::about::About;
-use components::projects::Projects;
+use clap::Error;
+use gloo::history::{BrowserHistory, History as _, HistoryListener, Location};
 use wasm_bindgen::prelude::*;
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlElement, HtmlInputElement};
 use yew::html::Scope;
 use yew::{Renderer, prelude::*};
 
-use cli::*;
-use components::conway::Conway;
-use components::differ::Differ;
-use components::history::History;
+use cli::{Invocation, command_error, parse_line, script_error};
+use commands::{Effect, Interrupts, REGISTRY, TerminalState};
 use std::ops::Add;
 use std::rc::Rc;
 use utils::ansi_html::convert;
-use utils::history_store::HistoryStore;
+use utils::history_store::{HistoryEntry, HistoryOptions, HistoryStore};
 
-use crate::utils::autocomplete::get_autocomplete;
+use crate::utils::alias_store::AliasStore;
+use crate::utils::autocomplete::{Completion, CompletionContext, common_prefix, get_autocomplete};
+use crate::utils::history_expansion::expand_history;
+use crate::utils::keymap::{KeyAction, KeyChord, Keymap};
+use crate::utils::line_edit::{self, byte_to_utf16, utf16_to_byte};
+use crate::utils::recall::{HistoryCursor, ReverseSearch};
+use crate::utils::route::{line_to_route, route_to_line};
+use crate::utils::startup_script::{STARTUP_FILE, StartupScript, script_lines};
+use crate::utils::tokenize::{Connector, TokenizeError, quote, split_chain};
+use crate::utils::transcript::Transcript;
+use crate::utils::variables::Variables;
+use crate::utils::vfs::ROOT;
+
+/// The number of entries kept in the terminal's scrollback
+const TRANSCRIPT_CAPACITY: usize = 50;
+
+/// How many scripts, or lines run again from the history, can run inside each other, so a script
+/// which sources itself stops
+const MAX_SCRIPT_DEPTH: usize = 8;
+
+/// The output of a command, or the error that stopped it from running
+type Output = Result<Invocation, Rc<Error>>;
+
+/// The completions being cycled through by pressing Tab
+struct TabCycle {
+    candidates: Vec<String>,
+    /// The candidate currently in the input, which is `None` until Tab is pressed again
+    index: Option<usize>,
+}
 
-#[derive(Debug, Clone)]
 pub struct App {
     input: String,
-    cmd: Option<Result<Cli, Rc<Error>>>,
-    autocomplete: Vec<(String, Option<String>)>,
+    /// Every line entered so far, and the results of each command that ran from it
+    transcript: Transcript<Output>,
+    /// Set when a new entry is added, so the transcript scrolls to it after rendering
+    scroll_pending: bool,
+    transcript_ref: NodeRef,
+    latest_ref: NodeRef,
+    autocomplete: Vec<Completion>,
     autocomplete_open: bool,
     autocomplete_selection: Option<usize>,
-    history: HistoryStore,
+    console_ref: NodeRef,
+    /// Where to move the console's cursor after rendering, as a byte index into the input
+    cursor_pending: Option<usize>,
+    /// The text cut from the console last, which can be pasted back with Ctrl-Y
+    killed: String,
+    /// Set while pressing Tab repeatedly to cycle through completions
+    tab_cycle: Option<TabCycle>,
+    state: TerminalState,
+    /// How many scripts are running inside each other
+    script_depth: usize,
+    history_cursor: HistoryCursor,
+    /// Set while a Ctrl-R search through history is in progress
+    reverse_search: Option<ReverseSearch>,
+    browser_history: BrowserHistory,
+    /// The URL of the line that ran last, so the app can tell its own navigation apart from the
+    /// user going back or forward
+    current_route: String,
+    _route_listener: HistoryListener,
 }
 
 #[derive(Debug)]
@@ -41,6 +88,23 @@ pub enum AppMsg {
     AutocompleteHover(usize),
     AutocompleteShift(isize),
     AutocompleteSelect,
+    AutocompleteClose,
+    TabComplete {
+        reverse: bool,
+    },
+    /// A bound key was pressed, with the cursor where it was in the console
+    Key {
+        action: KeyAction,
+        cursor: u32,
+    },
+    HistoryOlder,
+    HistoryNewer,
+    SearchNext,
+    SearchAccept,
+    SearchCancel,
+    RouteChanged,
+    RunLine(String),
+    RunScript(String),
     FormSubmit,
     None,
 }
@@ -49,22 +113,76 @@ impl Component for App {
     type Message = AppMsg;
     type Properties = ();
 
-    fn create(_: &Context<Self>) -> Self {
-        App {
+    fn create(context: &Context<Self>) -> Self {
+        let browser_history = BrowserHistory::new();
+        let link = context.link().clone();
+        let route_listener =
+            browser_history.listen(move || link.send_message(AppMsg::RouteChanged));
+
+        let mut app = App {
             input: String::new(),
-            cmd: None,
-            autocomplete: get_autocomplete("".into()),
+            transcript: Transcript::new(TRANSCRIPT_CAPACITY),
+            scroll_pending: false,
+            transcript_ref: NodeRef::default(),
+            latest_ref: NodeRef::default(),
+            autocomplete: Vec::new(),
             autocomplete_open: false,
             autocomplete_selection: None,
-            history: HistoryStore::new(),
+            console_ref: NodeRef::default(),
+            cursor_pending: None,
+            killed: String::new(),
+            tab_cycle: None,
+            state: TerminalState {
+                history: HistoryStore::new(),
+                aliases: AliasStore::new(),
+                variables: Variables::default(),
+                keymap: Keymap::new(),
+                cwd: ROOT.to_string(),
+                last_ok: true,
+                onrun: context.link().callback(AppMsg::RunLine),
+                onscript: context.link().callback(AppMsg::RunScript),
+                interrupts: 0,
+            },
+            script_depth: 0,
+            history_cursor: HistoryCursor::default(),
+            reverse_search: None,
+            current_route: location_route(&browser_history.location()),
+            browser_history,
+            _route_listener: route_listener,
+        };
+
+        app.refresh_autocomplete();
+
+        // warn about saved history that couldn't be read before anything replaces it
+        if app.state.history.corrupt().is_some() {
+            app.run_line("history check".to_string());
+        }
+
+        // run the startup script first, so anything it sets up is there for the URL's line
+        if script_lines(StartupScript::new().script()).next().is_some() {
+            app.run_line(format!("source {STARTUP_FILE}"));
+        }
+
+        // run the command linked to by the URL the page was opened with
+        if let Some(line) = app.route_line(&app.browser_history.location()) {
+            app.run_linked(line);
         }
+
+        app
     }
 
     fn update(&mut self, _: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             AppMsg::InputSet(s) => {
+                if let Some(search) = &mut self.reverse_search {
+                    search.set_query(s, self.state.history.entries());
+                    return true;
+                }
                 self.input = s;
-                self.autocomplete = get_autocomplete(self.input.clone());
+                self.autocomplete_open = true;
+                self.tab_cycle = None;
+                self.history_cursor.reset();
+                self.refresh_autocomplete();
                 self.autocomplete_selection = None;
                 true
             }
@@ -93,56 +211,117 @@ impl Component for App {
                 }
             }
             AppMsg::AutocompleteSelect => {
-                if let Some(selected) = self.autocomplete_selection {
-                    if let Some(autocomplete) = self.autocomplete.get(selected) {
-                        let mut parts: Vec<String> = self
-                            .input
-                            .split_whitespace()
-                            .map(|s| s.to_string())
-                            .collect();
-
-                        if self.input.ends_with(' ') {
-                            // User just finished a token, so append new one
-                            parts.push(autocomplete.0.clone());
-                        } else if let Some(last) = parts.last_mut() {
-                            // Replace last token
-                            *last = autocomplete.0.clone();
-                        } else {
-                            // No tokens yet
-                            parts.push(autocomplete.0.clone());
-                        }
-
-                        self.input = parts.join(" ");
-                        self.input.push(' ');
-                        self.autocomplete = get_autocomplete(self.input.clone());
-                        self.autocomplete_selection = None;
-                        return true;
-                    }
+                if let Some(selected) = self.autocomplete_selection
+                    && let Some(autocomplete) = self.autocomplete.get(selected)
+                {
+                    let value = autocomplete.value.clone();
+                    self.replace_last_token(&value);
+                    self.input.push(' ');
+                    self.tab_cycle = None;
+                    self.refresh_autocomplete();
+                    self.autocomplete_selection = None;
+                    return true;
                 }
                 false
             }
+            AppMsg::TabComplete { reverse } => self.tab_complete(reverse),
+            AppMsg::Key { action, cursor } => self.key_action(action, cursor),
+            AppMsg::AutocompleteClose => {
+                self.autocomplete_open = false;
+                self.autocomplete_selection = None;
+                true
+            }
+            AppMsg::HistoryOlder => {
+                match self
+                    .history_cursor
+                    .older(self.state.history.entries(), &self.input)
+                {
+                    Some(line) => {
+                        self.set_recalled(line);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            AppMsg::HistoryNewer => match self.history_cursor.newer(self.state.history.entries()) {
+                Some(line) => {
+                    self.set_recalled(line);
+                    true
+                }
+                None => false,
+            },
+            AppMsg::SearchNext => {
+                match &mut self.reverse_search {
+                    Some(search) => search.next(self.state.history.entries()),
+                    None => self.reverse_search = Some(ReverseSearch::new(self.input.clone())),
+                }
+                true
+            }
+            AppMsg::SearchAccept => {
+                if let Some(search) = self.reverse_search.take() {
+                    let line = search
+                        .matched(self.state.history.entries())
+                        .map(ToString::to_string)
+                        .unwrap_or(search.original);
+                    self.set_recalled(line);
+                }
+                true
+            }
+            AppMsg::SearchCancel => {
+                if let Some(search) = self.reverse_search.take() {
+                    self.set_recalled(search.original);
+                }
+                true
+            }
+            AppMsg::RouteChanged => {
+                let location = self.browser_history.location();
+                let route = location_route(&location);
+                if route == self.current_route {
+                    return false;
+                }
+                self.current_route = route;
+                match self.route_line(&location) {
+                    Some(line) => {
+                        self.run_linked(line);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            AppMsg::RunLine(line) => {
+                self.run_entered(line);
+                self.history_cursor.reset();
+                true
+            }
+            AppMsg::RunScript(script) => {
+                let mut outputs = Vec::new();
+                let cleared = self.run_script(&script, &mut outputs);
+                self.push_outputs("source".to_string(), outputs, cleared);
+                true
+            }
             AppMsg::FormSubmit => {
+                if let Some(search) = self.reverse_search.take() {
+                    // like bash, accepting a search with enter runs the match right away
+                    self.input = search
+                        .matched(self.state.history.entries())
+                        .map(ToString::to_string)
+                        .unwrap_or(search.original);
+                }
                 if let Some(selected) = self.autocomplete_selection {
                     if let Some(autocomplete) = self.autocomplete.get(selected) {
-                        self.input = autocomplete.0.clone();
-                        self.autocomplete = get_autocomplete(self.input.clone());
+                        self.input = autocomplete.value.clone();
+                        self.refresh_autocomplete();
                         self.autocomplete_selection = None;
                     } else {
                         return false;
                     }
                 } else {
-                    // add to history
-                    self.history.push(self.input.clone());
-
-                    // looks messy, fix up later
-                    let cmd =
-                        Cli::try_parse_from(self.input.to_lowercase().split_ascii_whitespace())
-                            .map_err(|e| Rc::new(e));
-                    let is_ok = cmd.is_ok();
-                    self.cmd = Some(cmd);
-                    if is_ok {
-                        self.execute_args();
-                    }
+                    let input = std::mem::take(&mut self.input);
+                    self.run_entered(input);
+                    self.tab_cycle = None;
+                    self.history_cursor.reset();
+                    self.refresh_autocomplete();
+                    self.autocomplete_open = false;
                 }
 
                 true
@@ -151,6 +330,25 @@ impl Component for App {
         }
     }
 
+    fn rendered(&mut self, _: &Context<Self>, _first_render: bool) {
+        if let Some(cursor) = self.cursor_pending.take()
+            && let Some(console) = self.console_ref.cast::<HtmlInputElement>()
+        {
+            let cursor = byte_to_utf16(&self.input, cursor);
+            let _ = console.set_selection_range(cursor, cursor);
+        }
+
+        if self.scroll_pending {
+            self.scroll_pending = false;
+            if let (Some(transcript), Some(latest)) = (
+                self.transcript_ref.cast::<HtmlElement>(),
+                self.latest_ref.cast::<HtmlElement>(),
+            ) {
+                transcript.set_scroll_top(latest.offset_top());
+            }
+        }
+    }
+
     fn view(&self, context: &Context<Self>) -> Html {
         html! {<>
             <header><h1>{"Portfolio Terminal"}</h1></header>
@@ -173,7 +371,9 @@ impl Component for App {
 
 impl App {
     fn view_input(&self, link: &Scope<Self>) -> Html {
-        let show_autocomplete = self.autocomplete_open && !self.autocomplete.is_empty();
+        let searching = self.reverse_search.is_some();
+        let show_autocomplete =
+            !searching && self.autocomplete_open && !self.autocomplete.is_empty();
         let selected_autocomplete = show_autocomplete && self.autocomplete_selection.is_some();
         let oninput = link.callback(|e: InputEvent| {
             AppMsg::InputSet(
@@ -185,30 +385,68 @@ impl App {
         let onfocus = link.callback(|_| AppMsg::InputFocus(true));
         let onblur = link.callback(|_| AppMsg::InputFocus(false));
 
-        let onkeydown = link.callback(move |e: KeyboardEvent| match e.key().as_str() {
-            "ArrowDown" => {
+        let keymap = self.state.keymap.clone();
+        let onkeydown = link.callback(move |e: KeyboardEvent| {
+            let chord = KeyChord::from_event(&e.key(), &e.code(), e.ctrl_key(), e.alt_key());
+            if let Some(action) = keymap.get(&chord) {
                 e.prevent_default();
-                AppMsg::AutocompleteShift(1)
-            }
-            "ArrowUp" => {
-                e.prevent_default();
-                AppMsg::AutocompleteShift(-1)
+                if action == KeyAction::ReverseSearchHistory {
+                    return AppMsg::SearchNext;
+                }
+                let cursor = e
+                    .target_dyn_into::<HtmlInputElement>()
+                    .and_then(|elem| elem.selection_start().ok().flatten())
+                    .unwrap_or(u32::MAX);
+                return AppMsg::Key { action, cursor };
             }
-            "Enter" => {
-                if selected_autocomplete {
+
+            match e.key().as_str() {
+                "Escape" if searching => AppMsg::SearchCancel,
+                "ArrowDown" | "ArrowUp" | "ArrowLeft" | "ArrowRight" | "Tab" if searching => {
                     e.prevent_default();
-                    AppMsg::AutocompleteSelect
-                } else {
-                    AppMsg::None
+                    AppMsg::SearchAccept
+                }
+                "Escape" => AppMsg::AutocompleteClose,
+                "Tab" => {
+                    e.prevent_default();
+                    AppMsg::TabComplete {
+                        reverse: e.shift_key(),
+                    }
+                }
+                "ArrowDown" if show_autocomplete => {
+                    e.prevent_default();
+                    AppMsg::AutocompleteShift(1)
+                }
+                "ArrowUp" if show_autocomplete => {
+                    e.prevent_default();
+                    AppMsg::AutocompleteShift(-1)
+                }
+                "ArrowDown" => {
+                    e.prevent_default();
+                    AppMsg::HistoryNewer
                 }
+                "ArrowUp" => {
+                    e.prevent_default();
+                    AppMsg::HistoryOlder
+                }
+                "Enter" => {
+                    if selected_autocomplete {
+                        e.prevent_default();
+                        AppMsg::AutocompleteSelect
+                    } else {
+                        AppMsg::None
+                    }
+                }
+                _ => AppMsg::None,
             }
-            _ => AppMsg::None,
         });
 
         let get_option_class = |index: usize| {
-            (Some(index) == self.autocomplete_selection)
-                .then(|| "option-selected")
-                .unwrap_or("")
+            if Some(index) == self.autocomplete_selection {
+                "option-selected"
+            } else {
+                ""
+            }
         };
 
         html! {
@@ -219,19 +457,24 @@ impl App {
                     AppMsg::FormSubmit
                 })}
             >
-                <label for="console">{"Command"}</label>
+                <label for="console">{if searching { "(reverse-i-search)" } else { "Command" }}</label>
                 <div id="console-wrapper">
                     <input
                         id="console"
                         autofocus=true
                         placeholder="..."
                         autocomplete="off"
-                        value={self.input.clone()}
+                        value={match &self.reverse_search {
+                            Some(search) => search.query.clone(),
+                            None => self.input.clone(),
+                        }}
+                        ref={self.console_ref.clone()}
                         oninput={oninput}
                         onfocus={onfocus}
                         onblur={onblur}
                         onkeydown={onkeydown}
                     />
+                    {self.view_reverse_search()}
                     {if show_autocomplete {
                         html! {
                             <ul class="autocomplete">
@@ -248,10 +491,10 @@ impl App {
                                             onmousedown={onmousedown}
                                         >
                                             <div class="option-value">
-                                                {&completion.0}
+                                                {Self::view_completion_value(completion)}
                                             </div>
                                             <div class="option-description">
-                                                {completion.1.as_ref().map(|s| s.as_str()).unwrap_or("")}
+                                                {completion.description.as_deref().unwrap_or("")}
                                             </div>
                                         </li>
                                     }
@@ -264,128 +507,392 @@ impl App {
         }
     }
 
+    /// Shows a completion's value, highlighting the characters that matched the input
+    fn view_completion_value(completion: &Completion) -> Html {
+        completion
+            .value
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if completion.matched.contains(&i) {
+                    html! { <span class="option-match">{c}</span> }
+                } else {
+                    html! { {c} }
+                }
+            })
+            .collect()
+    }
+
+    fn view_reverse_search(&self) -> Html {
+        let Some(search) = &self.reverse_search else {
+            return html! {};
+        };
+        match search.matched(self.state.history.entries()) {
+            Some(line) => html! {
+                <div class="reverse-search">{line}</div>
+            },
+            None if search.query.is_empty() => html! {
+                <div class="reverse-search">{"type to search history"}</div>
+            },
+            None => html! {
+                <div class="reverse-search failing">{"no matching history"}</div>
+            },
+        }
+    }
+
     fn view_main(&self) -> Html {
-        html! { <main role="main"> {
-            match self.cmd.as_ref() {
-                Some(Ok(args)) => html! {self.view_cmd(args)},
-                // TODO: better error rendering
-                Some(Err(err)) => convert(Rc::as_ref(err)),
-                None => html! {},
+        let latest_id = self.transcript.latest().map(|entry| entry.id);
+        html! { <main role="main" id="transcript" ref={self.transcript_ref.clone()}>
+            <ContextProvider<Interrupts> context={Interrupts(self.state.interrupts)}> {
+                for self.transcript.entries().map(|entry| {
+                    let node_ref = if Some(entry.id) == latest_id {
+                        self.latest_ref.clone()
+                    } else {
+                        NodeRef::default()
+                    };
+                    html! { <div class="transcript-entry" key={entry.id} ref={node_ref}>
+                        <div class="prompt-line">
+                            <span class="prompt">{"$\u{a0}"}</span>{&entry.input}
+                        </div>
+                        { for entry.outputs.iter().map(|cmd| html! { <div class="command-output">{
+                            match cmd {
+                                Ok(invocation) => invocation.output.clone(),
+                                // TODO: better error rendering
+                                Err(err) => convert(Rc::as_ref(err)),
+                            }
+                        }</div> }) }
+                    </div> }
+                })
+            } </ContextProvider<Interrupts>>
+        </main> }
+    }
+
+    /// Runs a line the user entered, then records it in the history along with whether it
+    /// succeeded. References to history like `!!` are expanded first, and the transcript shows
+    /// the line they expanded to.
+    fn run_entered(&mut self, line: String) {
+        let line = match expand_history(&line, self.state.history.entries()) {
+            Ok(expanded) => expanded.unwrap_or(line),
+            Err(err) => {
+                self.state.last_ok = false;
+                self.push_entry(line, vec![Err(Rc::new(command_error(err)))]);
+                return;
             }
-        } </main> }
+        };
+        self.push_route(&line);
+        let input = line.clone();
+        self.run_line(line);
+        let entry = HistoryEntry {
+            input,
+            time: Some(js_sys::Date::now()),
+            ok: self.state.last_ok,
+        };
+        let options = HistoryOptions::from_variables(|name| self.state.variables.get(name));
+        self.state.history.push(entry, options);
+    }
+
+    /// Gets the line a URL runs, where paths are only read as a line when they start with the name
+    /// of a command or alias
+    fn route_line(&self, location: &Location) -> Option<String> {
+        route_to_line(
+            location.path(),
+            location.query_str(),
+            location.hash(),
+            |word| {
+                REGISTRY
+                    .commands()
+                    .any(|command| command.name().eq_ignore_ascii_case(word))
+                    || self.state.aliases.get(word).is_some()
+            },
+        )
+    }
+
+    /// Runs a line from the URL. Lines that would change anything are put in the console for the
+    /// visitor to run themselves instead, so a link can't change their settings.
+    fn run_linked(&mut self, line: String) {
+        if self.is_read_only(&line) {
+            self.run_line(line);
+            return;
+        }
+        let err = command_error(
+            "links can only run commands that don't change anything, so this line was put in the \
+             console for you to run yourself",
+        );
+        self.push_entry(line.clone(), vec![Err(Rc::new(err))]);
+        self.set_recalled(line);
+    }
+
+    /// Whether every command chained on a line, after expanding aliases, only shows things.
+    /// Commands that can't be parsed never run, so they don't count.
+    fn is_read_only(&self, line: &str) -> bool {
+        let Ok(commands) = self.expand_line(line) else {
+            return true;
+        };
+        commands.iter().all(|(_, command)| {
+            parse_line(command, |name| self.state.variable(name))
+                .map(|invocation| invocation.command.read_only(&invocation.matches))
+                .unwrap_or(true)
+        })
+    }
+
+    /// Runs a line and adds its output to the transcript
+    fn run_line(&mut self, line: String) {
+        let mut outputs = Vec::new();
+        let cleared = self.run_commands(&line, &mut outputs);
+        self.push_outputs(line, outputs, cleared);
     }
 
-    fn view_cmd(&self, args: &Cli) -> Html {
-        match &args.command {
-            Command::About => html! { <About /> },
-            Command::Contact => {
-                html! { <>
-                    <h2>{"You can send me emails here:"}</h2>
-                    <a href="mailto:Benjaminjahall@gmail.com">{"Benjaminjahall@gmail.com"}</a>
-                    <br />
-                    <br />
-
-                    <h2>{"My projects and code are documented here:"}</h2>
-                    <a href="https://github.com/benhall-7">{"benhall-7"}</a>
-                    <br />
-                    <br />
-
-                    <h2>{"Obligatory LinkedIn:"}</h2>
-                    <a href="https://www.linkedin.com/in/benjaminjahall/">{"benjaminjahall"}</a>
-                    <br />
-                    <br />
-
-                    <h2>{"Check out my observations on iNaturalist:"}</h2>
-                    <a href="https://www.inaturalist.org/observations?user_id=benhall-7&verifiable=any">{"benhall-7"}</a>
-                </> }
+    /// Runs each command chained together on a line, skipping any that are short-circuited by
+    /// `&&` or `||`, and adds their output to `outputs`. Returns whether the transcript was
+    /// cleared along the way.
+    fn run_commands(&mut self, line: &str, outputs: &mut Vec<Output>) -> bool {
+        let commands = match self.expand_line(line) {
+            Ok(commands) => commands,
+            Err(err) => {
+                self.state.last_ok = false;
+                outputs.push(Err(Rc::new(command_error(err))));
+                return false;
             }
-            Command::Skills => {
-                html! {
-                    for skills::SKILLS.iter().map(|skill| {
-                        html! { <>
-                            <h2>{skill.category}</h2>
-                            <ul>{ for skill.bullets.iter().map(|b| html! {<li>{b}</li>}) }</ul>
-                        </> }
-                    })
-                }
+        };
+
+        let mut cleared = false;
+        for (connector, command) in commands {
+            if !connector.should_run(self.state.last_ok) {
+                continue;
             }
-            Command::Projects => html! { <Projects /> },
-            Command::History(history) => {
-                if let Some(sub) = &history.command {
-                    match sub {
-                        HistorySubcommand::Clear => {
-                            html! { <p>{"History cleared"}</p> }
-                        }
-                        HistorySubcommand::Index { num } => {
-                            html! {
-                                <History items={self.history.history()} index={num} />
-                            }
-                        }
+            let result = parse_line(&command, |name| self.state.variable(name)).and_then(
+                |mut invocation| {
+                    let effect = invocation
+                        .command
+                        .execute(&invocation.matches, &mut self.state)?;
+                    invocation.output = invocation.command.view(&invocation.matches, &self.state);
+                    Ok((invocation, effect))
+                },
+            );
+            self.state.last_ok = result.is_ok();
+            match result {
+                Ok((_, Effect::ClearTranscript)) => {
+                    // clearing also removes the output of any earlier commands on this line
+                    self.transcript.clear();
+                    outputs.clear();
+                    cleared = true;
+                }
+                Ok((invocation, Effect::RunScript(script))) => {
+                    outputs.push(Ok(invocation));
+                    if self.script_depth >= MAX_SCRIPT_DEPTH {
+                        self.state.last_ok = false;
+                        outputs.push(Err(Rc::new(command_error(format!(
+                            "scripts can only be nested {MAX_SCRIPT_DEPTH} deep"
+                        )))));
+                    } else {
+                        cleared |= self.run_script(&script, outputs);
                     }
-                } else {
-                    html! {
-                        <History items={self.history.history()} />
+                }
+                Ok((invocation, Effect::RunLine(line))) => {
+                    outputs.push(Ok(invocation));
+                    if self.script_depth >= MAX_SCRIPT_DEPTH {
+                        self.state.last_ok = false;
+                        outputs.push(Err(Rc::new(command_error(format!(
+                            "lines can only run each other {MAX_SCRIPT_DEPTH} deep"
+                        )))));
+                    } else {
+                        self.script_depth += 1;
+                        cleared |= self.run_commands(&line, outputs);
+                        self.script_depth -= 1;
                     }
                 }
+                Ok((invocation, Effect::None)) => outputs.push(Ok(invocation)),
+                Err(err) => outputs.push(Err(Rc::new(err))),
             }
-            Command::Diff => html! { <Differ /> },
-            Command::Conway(ConwayArg { about }) => {
-                if !about {
-                    html! { <Conway /> }
-                } else {
-                    html! { <div class="conway-about">
-                        <h2>{"Conway's Game of Life"}</h2>
-                        <p>
-                            {"Devised by the late British mathematician "}
-                            <a href="https://en.wikipedia.org/wiki/John_Horton_Conway">{"John Horton Conway"}</a>
-                            {", the Game of Life is a simple 2D simulation of cells treated as organisms. These cells follow a simple set of mathematical rules meant to emulate basic rules of populations, determining whether they are considered \"alive\" or \"dead\". The rules are as follows:"}
-                        </p>
-                        <ul>
-                            <li>
-                                {"If a living cell has 2 or 3 neighbors ('neighbors' includes diagonals), it remains living. Otherwise, it dies"}
-                            </li>
-                            <li>
-                                {"If a dead cell (or rather, empty cell) has 3 neighbors exactly, it becomes alive. Otherwise it remains dead."}
-                            </li>
-                        </ul>
-                        <p>
-                            {"In this way, a strategically placed set of cells may remain in existence forever, in a loop. Many configurations of cells die off completely; many explode first and can lead into either outcome."}
-                            <br />
-                            {"The Game of Life is only one such example of a broader topic of interest, known as "}
-                            <a href="https://en.wikipedia.org/wiki/Cellular_automaton">{"Cellular Automata."}</a>
-                            {" Cellular Automata (CA for short) describes systems of these cells in grids (which may not necessarily be 2D) and how they behave under certain other rulesets. These are useful in some branches of science for examining behavior of real systems such as those studied under Biology or Chemistry."}
-                            <br />
-                            {"Another interesting property of the game is that it exhibits Turing Completeness, a quality of a system by which you can perform arbitrary calculations. This is made possible through cell patterns such as the Gosper Gun, which if set up correctly may emulate a NAND gate instruction, an essential logical operator seen in hardware necessary to run machine instructions. Note that we also give ourselves infinite time and grid space when discussing the game's Turing Completeness. This means also that it is possible to replicate the Game of Life "}
-                            <a href="https://www.youtube.com/watch?v=xP5-iIeKXE8">{"inside the Game of Life itself"}</a>
-                            {" with huge blocks of cells that work to calculate the results of their neighbor blocks."}
-                        </p>
-                        <h2>{"Implementation"}</h2>
-                        <p>
-                            {"The implementation on this website was designed initially with a Rust backend and Javascript frontend. Since then, it has been rewritten entirely in Rust using the Yew framework. This is made possible using a recent browser standard called "}
-                            <a href="https://webassembly.org/">{"WebAssembly (WASM)"}</a>
-                            {", which provides a compilation target for languages designed for native performance apps such as C, C++, and of course Rust."}
-                            <br />
-                        </p>
-                    </div> }
+        }
+        cleared
+    }
+
+    /// Runs each line of a script, labelling any errors with the line they came from. Returns
+    /// whether the transcript was cleared along the way.
+    fn run_script(&mut self, script: &str, outputs: &mut Vec<Output>) -> bool {
+        self.script_depth += 1;
+        let mut cleared = false;
+        for (number, line) in script_lines(script) {
+            let start = outputs.len();
+            cleared |= self.run_commands(line, outputs);
+            for output in outputs.iter_mut().skip(start) {
+                if let Err(err) = output {
+                    *err = Rc::new(script_error(number, err));
                 }
             }
         }
+        self.script_depth -= 1;
+        cleared
     }
 
-    /// logic pertaining to state-altering command side effects
-    fn execute_args(&mut self) {
-        match &self.cmd {
-            Some(Ok(Cli {
-                command:
-                    Command::History(HistoryArg {
-                        command: Some(HistorySubcommand::Clear),
-                    }),
-            })) => {
-                self.history.clear();
+    /// Adds the outputs of a line to the transcript, unless it only cleared the transcript
+    fn push_outputs(&mut self, input: String, outputs: Vec<Output>, cleared: bool) {
+        if !(cleared && outputs.is_empty()) {
+            self.push_entry(input, outputs);
+        }
+    }
+
+    /// Splits a line into its chained commands, expanding the aliases at the start of each. An
+    /// alias may itself contain chained commands.
+    fn expand_line(&self, line: &str) -> Result<Vec<(Connector, String)>, TokenizeError> {
+        let mut commands = Vec::new();
+        for (connector, command) in split_chain(line)? {
+            let expanded = self.state.aliases.expand(command);
+            for (i, (inner, part)) in split_chain(&expanded)?.into_iter().enumerate() {
+                let connector = if i == 0 { connector } else { inner };
+                commands.push((connector, part.to_string()));
+            }
+        }
+        Ok(commands)
+    }
+
+    /// Records a line in the browser's history, so it can be linked to or returned to later
+    fn push_route(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            return;
+        }
+        let route = line_to_route(line);
+        if route != self.current_route {
+            self.current_route = route.clone();
+            self.browser_history.push(route);
+        }
+    }
+
+    /// Runs an action bound to a key. Editing keys pressed during a reverse search accept the
+    /// match first, like in bash.
+    fn key_action(&mut self, action: KeyAction, cursor: u32) -> bool {
+        match action {
+            KeyAction::ClearScreen => self.transcript.clear(),
+            KeyAction::Cancel => {
+                let line = match self.reverse_search.take() {
+                    Some(search) => search.original,
+                    None => std::mem::take(&mut self.input),
+                };
+                self.push_entry(format!("{line}^C"), Vec::new());
+                self.state.interrupts += 1;
+                self.state.last_ok = false;
+                self.history_cursor.reset();
+                self.set_recalled(String::new());
+            }
+            _ => {
+                let mut cursor = utf16_to_byte(&self.input, cursor);
+                if let Some(search) = self.reverse_search.take() {
+                    let line = search
+                        .matched(self.state.history.entries())
+                        .map(ToString::to_string)
+                        .unwrap_or(search.original);
+                    cursor = line.len();
+                    self.set_recalled(line);
+                }
+                if line_edit::apply(action, &mut self.input, &mut cursor, &mut self.killed) {
+                    self.tab_cycle = None;
+                    self.history_cursor.reset();
+                    self.refresh_autocomplete();
+                    self.autocomplete_selection = None;
+                }
+                self.cursor_pending = Some(cursor);
+            }
+        }
+        true
+    }
+
+    /// Completes the token being typed like readline. The first press inserts the longest prefix
+    /// shared by all the completions, and each press after that cycles through them.
+    fn tab_complete(&mut self, reverse: bool) -> bool {
+        if let Some(cycle) = &mut self.tab_cycle {
+            let size = cycle.candidates.len();
+            let index = match (cycle.index, reverse) {
+                (None, false) => 0,
+                (None, true) => size - 1,
+                (Some(i), false) => (i + 1) % size,
+                (Some(i), true) => (i + size - 1) % size,
+            };
+            cycle.index = Some(index);
+            let value = cycle.candidates[index].clone();
+            self.replace_last_token(&value);
+            // the list stays as it was, so the selection follows along with the cycle
+            self.autocomplete_selection = self.autocomplete.iter().position(|c| c.value == value);
+            self.autocomplete_open = true;
+            return true;
+        }
+
+        let candidates: Vec<String> = self.autocomplete.iter().map(|c| c.value.clone()).collect();
+        match candidates.as_slice() {
+            [] => false,
+            [only] => {
+                let only = only.clone();
+                self.replace_last_token(&only);
+                self.input.push(' ');
+                self.refresh_autocomplete();
+                self.autocomplete_selection = None;
+                true
+            }
+            _ => {
+                let token = self.last_token().to_lowercase();
+                let prefix = common_prefix(&candidates);
+                if prefix.len() > token.len() && prefix.to_lowercase().starts_with(&token) {
+                    self.replace_last_token(&prefix);
+                }
+                self.tab_cycle = Some(TabCycle {
+                    candidates,
+                    index: None,
+                });
+                self.autocomplete_open = true;
+                true
             }
-            _ => {}
         }
     }
+
+    /// The token at the end of the input, which is being completed
+    fn last_token(&self) -> &str {
+        let prefix = self.input.trim_end_matches(|c: char| !c.is_whitespace());
+        &self.input[prefix.len()..]
+    }
+
+    /// Replaces the token at the end of the input. Everything before it is kept as is, so quoted
+    /// arguments are untouched.
+    fn replace_last_token(&mut self, value: &str) {
+        let prefix_len = self.input.len() - self.last_token().len();
+        self.input.truncate(prefix_len);
+        self.input.push_str(&quote(value));
+    }
+
+    fn refresh_autocomplete(&mut self) {
+        let context = CompletionContext {
+            aliases: &self.state.aliases,
+            history: self.state.history.entries(),
+            variables: &self.state.variables,
+            keymap: &self.state.keymap,
+            cwd: &self.state.cwd,
+            given: &[],
+        };
+        self.autocomplete = get_autocomplete(&self.input, &context);
+    }
+
+    /// Shows a line recalled from history in the console, without opening autocomplete for it
+    fn set_recalled(&mut self, line: String) {
+        self.input = line;
+        self.tab_cycle = None;
+        self.refresh_autocomplete();
+        self.autocomplete_open = false;
+        self.autocomplete_selection = None;
+    }
+
+    fn push_entry(&mut self, input: String, outputs: Vec<Output>) {
+        self.transcript.push(input, outputs);
+        self.scroll_pending = true;
+    }
+}
+
+/// Gets the full route of a location, as written by [`line_to_route`]
+fn location_route(location: &Location) -> String {
+    format!(
+        "{}{}{}",
+        location.path(),
+        location.query_str(),
+        location.hash()
+    )
 }
 
 #[wasm_bindgen(start)]
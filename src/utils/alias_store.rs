@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+use crate::utils::storage::MemoryStore;
+use crate::utils::storage::{KeyValueStore, LocalStore};
+
+const ALIAS_KEY: &str = "portfolio.aliases";
+
+/// The shape aliases are saved in
+#[derive(Serialize, Deserialize)]
+struct SavedAliases<T> {
+    aliases: T,
+}
+
+/// User-defined shorthands for commands, which replace the first word of a command before it's
+/// parsed. They're saved in a [`KeyValueStore`], which is LocalStorage by default.
+#[derive(Debug, Clone, Default)]
+pub struct AliasStore<S = LocalStore> {
+    store: S,
+    aliases: BTreeMap<String, String>,
+}
+
+impl AliasStore {
+    pub fn new() -> Self {
+        AliasStore::load(LocalStore)
+    }
+}
+
+impl<S: KeyValueStore> AliasStore<S> {
+    /// Reads the aliases saved in a store, which they're saved back to as they change
+    pub fn load(store: S) -> Self {
+        let aliases = store
+            .get_json::<SavedAliases<_>>(ALIAS_KEY)
+            .map(|saved| saved.aliases)
+            .unwrap_or_default();
+        AliasStore { store, aliases }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.aliases.get(name).map(String::as_str)
+    }
+
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.aliases.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn set(&mut self, name: String, expansion: String) {
+        self.aliases.insert(name, expansion);
+        self.save();
+    }
+
+    /// Removes an alias, returning its expansion if it existed
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let removed = self.aliases.remove(name);
+        if removed.is_some() {
+            self.save();
+        }
+        removed
+    }
+
+    fn save(&mut self) {
+        let saved = SavedAliases {
+            aliases: &self.aliases,
+        };
+        self.store.set_json(ALIAS_KEY, &saved);
+    }
+
+    /// Replaces the first word of a command with its alias. The expansion's own first word is
+    /// expanded again, unless it names an alias that was already expanded, so aliases may refer to
+    /// each other without looping forever.
+    pub fn expand(&self, command: &str) -> String {
+        let mut command = command.trim_start().to_string();
+        let mut expanded: Vec<String> = Vec::new();
+
+        loop {
+            let word_end = command.find(char::is_whitespace).unwrap_or(command.len());
+            let word = &command[..word_end];
+            // quoting or escaping the first word prevents it from being expanded
+            if word.contains(['\'', '"', '\\']) || expanded.iter().any(|e| e == word) {
+                break;
+            }
+            let Some(expansion) = self.get(word) else {
+                break;
+            };
+            expanded.push(word.to_string());
+            command = format!("{}{}", expansion.trim_start(), &command[word_end..]);
+        }
+
+        command
+    }
+}
+
+/// Splits an `alias` definition like `name=expansion` into its name and expansion
+pub fn parse_definition(definition: &str) -> Result<(&str, &str), String> {
+    let (name, expansion) = definition.split_once('=').ok_or_else(|| {
+        format!("expected a definition in the form name=expansion, got '{definition}'")
+    })?;
+    if !is_valid_name(name) {
+        return Err(format!("'{name}' is not a valid alias name"));
+    }
+    Ok((name, expansion))
+}
+
+/// Alias names are limited to characters that can't be confused with quoting or operators
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+#[cfg(test)]
+fn test_store() -> AliasStore<MemoryStore> {
+    AliasStore {
+        store: MemoryStore::default(),
+        aliases: [
+            ("life", "conway --about"),
+            ("hw", r#"diff "Hello World" "hello world""#),
+            ("about", "about && contact"),
+            ("ping", "pong"),
+            ("pong", "ping"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect(),
+    }
+}
+
+#[test]
+fn test_expand() {
+    let store = test_store();
+    assert_eq!(store.expand("life"), "conway --about");
+    assert_eq!(store.expand("  hw"), r#"diff "Hello World" "hello world""#);
+    assert_eq!(store.expand("life --extra"), "conway --about --extra");
+    assert_eq!(store.expand("skills"), "skills");
+    assert_eq!(store.expand("'life'"), "'life'");
+}
+
+#[test]
+fn test_expand_recursion() {
+    let store = test_store();
+    // an alias may use the command it shadows
+    assert_eq!(store.expand("about"), "about && contact");
+    assert_eq!(store.expand("ping"), "ping");
+}
+
+#[test]
+fn test_parse_definition() {
+    assert_eq!(
+        parse_definition("life=conway --about"),
+        Ok(("life", "conway --about"))
+    );
+    assert_eq!(parse_definition("empty="), Ok(("empty", "")));
+    assert!(parse_definition("life").is_err());
+    assert!(parse_definition("=conway").is_err());
+    assert!(parse_definition("two words=conway").is_err());
+}
+
+#[test]
+fn test_alias_storage() {
+    let mut store = AliasStore::load(MemoryStore::default());
+    store.set("ll".to_string(), "ls".to_string());
+    store.set("life".to_string(), "conway".to_string());
+    store.remove("life");
+    let saved = store.store.get(ALIAS_KEY);
+    assert_eq!(saved.as_deref(), Some(r#"{"aliases":{"ll":"ls"}}"#));
+    assert_eq!(AliasStore::load(store.store).get("ll"), Some("ls"));
+}
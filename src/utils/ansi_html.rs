@@ -1,71 +1,273 @@
-use std::cell::LazyCell;
+//! Renders text containing ANSI escape codes as HTML. SGR sequences (`ESC[...m`) are followed
+//! through a small state machine which tracks the current style, and each run of text is given a
+//! span with classes for its attributes and inline colors. Any other escape sequences are dropped.
 
 use clap::Error;
-use regex::Regex;
-use yew::{Html, html};
-
-const COLOR_REGEX: LazyCell<Regex> =
-    LazyCell::new(|| Regex::new(r"\u{1b}\[(?P<fg>\d{1,2})(;\d{1,2})?m").unwrap());
-
-#[derive(Debug, Clone, Copy)]
-enum Color {
-    None,
-    Red,
-    Green,
-    Yellow,
+use yew::{Html, classes, html};
+
+const ESC: char = '\u{1b}';
+
+/// A color set by an SGR sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnsiColor {
+    /// One of the 256 colors of the xterm palette, where the first 16 follow the page's theme
+    Indexed(u8),
+    Rgb(u8, u8, u8),
 }
 
-impl<'a> From<&'a str> for Color {
-    fn from(text: &str) -> Self {
-        match text {
-            "31" => Color::Red,
-            "32" => Color::Green,
-            "33" => Color::Yellow,
-            _ => Color::None,
+impl AnsiColor {
+    /// Writes the color as a CSS value
+    fn css(self) -> String {
+        match self {
+            AnsiColor::Indexed(n @ 0..=15) => format!("var(--ansi-{n})"),
+            AnsiColor::Indexed(n @ 16..=231) => {
+                // a 6x6x6 cube, where each level above zero is 40 apart starting from 95
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                let n = n - 16;
+                format!(
+                    "rgb({}, {}, {})",
+                    level(n / 36),
+                    level(n / 6 % 6),
+                    level(n % 6)
+                )
+            }
+            AnsiColor::Indexed(n) => {
+                let gray = 8 + (n - 232) * 10;
+                format!("rgb({gray}, {gray}, {gray})")
+            }
+            AnsiColor::Rgb(r, g, b) => format!("rgb({r}, {g}, {b})"),
         }
     }
 }
 
-pub fn convert(err: &Error) -> Html {
-    let ansi_str = format!("{}", err.render().ansi());
-    let mut chunks: Vec<(Color, &str)> = Vec::new();
-    let mut last_text = 0;
-    let mut last_color = Color::None;
-
-    COLOR_REGEX
-        .captures_iter(&ansi_str)
-        .zip(COLOR_REGEX.find_iter(&ansi_str))
-        .for_each(|(capture, m)| {
-            if m.start() > last_text {
-                chunks.push((last_color, &ansi_str[last_text..m.start()]));
+/// The text attributes in effect at some point in the output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnsiStyle {
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub reverse: bool,
+    pub strikethrough: bool,
+    pub fg: Option<AnsiColor>,
+    pub bg: Option<AnsiColor>,
+}
+
+impl AnsiStyle {
+    /// Applies the parameters of an SGR sequence, each along with the sub-parameters written after
+    /// it with `:`. An empty parameter counts as 0, so `ESC[m` resets everything like `ESC[0m`
+    /// does.
+    fn apply(&mut self, params: &[Vec<Option<u16>>]) {
+        if params.is_empty() {
+            *self = AnsiStyle::default();
+        }
+        let mut params = params.iter();
+        while let Some(param) = params.next() {
+            let (code, subs) = param.split_first().unwrap_or((&None, &[]));
+            let code = code.unwrap_or(0);
+            match code {
+                0 => *self = AnsiStyle::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                // `4:0` turns underlining off, and other styles like `4:3` are drawn plainly
+                4 => self.underline = subs.first() != Some(&Some(0)),
+                7 => self.reverse = true,
+                9 => self.strikethrough = true,
+                21 | 22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.reverse = false,
+                29 => self.strikethrough = false,
+                30..=37 => self.fg = Some(AnsiColor::Indexed((code - 30) as u8)),
+                38 => self.fg = extended_color(subs, &mut params),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(AnsiColor::Indexed((code - 40) as u8)),
+                48 => self.bg = extended_color(subs, &mut params),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(AnsiColor::Indexed((code - 90 + 8) as u8)),
+                100..=107 => self.bg = Some(AnsiColor::Indexed((code - 100 + 8) as u8)),
+                // blinking, fonts and the like have no equivalent here
+                _ => {}
+            }
+        }
+    }
+
+    fn classes(&self) -> Vec<&'static str> {
+        [
+            (self.bold, "ansi-bold"),
+            (self.dim, "ansi-dim"),
+            (self.italic, "ansi-italic"),
+            (self.underline, "ansi-underline"),
+            (self.strikethrough, "ansi-strikethrough"),
+        ]
+        .into_iter()
+        .filter_map(|(set, class)| set.then_some(class))
+        .collect()
+    }
+
+    /// Writes the colors as inline CSS, swapping them if the style is reversed
+    fn css(&self) -> String {
+        let fg = self.fg.map(AnsiColor::css);
+        let bg = self.bg.map(AnsiColor::css);
+        let (fg, bg) = if self.reverse {
+            (
+                Some(bg.unwrap_or_else(|| "var(--bg-col)".to_string())),
+                Some(fg.unwrap_or_else(|| "var(--text-col)".to_string())),
+            )
+        } else {
+            (fg, bg)
+        };
+        let mut css = String::new();
+        if let Some(fg) = fg {
+            css.push_str(&format!("color: {fg};"));
+        }
+        if let Some(bg) = bg {
+            css.push_str(&format!("background-color: {bg};"));
+        }
+        css
+    }
+}
+
+/// Reads the color of a `38` or `48` parameter. It's written either in the parameter's own
+/// sub-parameters, as `38:5:n` or `38:2:[space]:r:g:b` where the color space is optional, or in
+/// the parameters after it, as `38;5;n` or `38;2;r;g;b`. Anything malformed resets the color.
+fn extended_color<'a>(
+    subs: &[Option<u16>],
+    params: &mut impl Iterator<Item = &'a Vec<Option<u16>>>,
+) -> Option<AnsiColor> {
+    let value = |p: Option<u16>| u8::try_from(p.unwrap_or(0)).ok();
+    if let Some((mode, rest)) = subs.split_first() {
+        return match (mode.unwrap_or(0), rest) {
+            (5, [n, ..]) => value(*n).map(AnsiColor::Indexed),
+            (2, [_, r, g, b, ..] | [r, g, b]) => {
+                Some(AnsiColor::Rgb(value(*r)?, value(*g)?, value(*b)?))
+            }
+            _ => None,
+        };
+    }
+    let mut next = || params.next().and_then(|p| value(p[0]));
+    match next()? {
+        5 => next().map(AnsiColor::Indexed),
+        2 => Some(AnsiColor::Rgb(next()?, next()?, next()?)),
+        _ => None,
+    }
+}
+
+/// Splits text into runs which share a style, leaving out the escape sequences
+pub fn parse(text: &str) -> Vec<(AnsiStyle, String)> {
+    let mut runs: Vec<(AnsiStyle, String)> = Vec::new();
+    let mut style = AnsiStyle::default();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != ESC {
+            match runs.last_mut() {
+                Some((last, run)) if *last == style => run.push(c),
+                _ => runs.push((style, c.to_string())),
+            }
+            continue;
+        }
+        if chars.next_if_eq(&'[').is_none() {
+            // a two character escape, like ESC c
+            chars.next();
+            continue;
+        }
+
+        // a control sequence is parameter bytes followed by a final byte from @ to ~
+        let mut params = String::new();
+        let mut final_byte = None;
+        for c in chars.by_ref() {
+            if ('@'..='~').contains(&c) {
+                final_byte = Some(c);
+                break;
             }
-            let color = Color::from(capture.name("fg").unwrap().as_str());
-
-            last_text = m.end();
-            last_color = color;
-        });
-    // append remainder of string
-    if last_text < ansi_str.len() {
-        chunks.push((last_color, &ansi_str[last_text..]));
+            params.push(c);
+        }
+        if final_byte == Some('m') {
+            let params: Vec<Vec<Option<u16>>> = params
+                .split(';')
+                .map(|param| param.split(':').map(|p| p.parse().ok()).collect())
+                .collect();
+            // a lone empty parameter is the same as none at all
+            let params = if params == [vec![None]] {
+                &[][..]
+            } else {
+                &params
+            };
+            style.apply(params);
+        }
     }
+    runs
+}
 
-    // get start and end ranges for colors
-
-    chunks
-        .iter()
-        .map(|(color, text)| {
-            let text_html = text.chars().map(|c| match c {
-                '\n' => html! {<br />},
-                '\t' => html! {"\u{a0}\u{a0}\u{a0}\u{a0}"},
-                ' ' => html! {"\u{a0}"},
-                _ => html! {c},
-            });
-            match color {
-                Color::None => html! {for text_html},
-                Color::Red => html! {<span class="highlight-red">{for text_html}</span>},
-                Color::Green => html! {<span class="highlight-green">{for text_html}</span>},
-                Color::Yellow => html! {<span class="highlight-yellow">{for text_html}</span>},
+/// Renders text containing ANSI escape codes as HTML
+pub fn ansi_to_html(text: &str) -> Html {
+    html! { <span class="ansi-output">{
+        for parse(text).into_iter().map(|(style, run)| {
+            if style == AnsiStyle::default() {
+                return html! { {run} };
+            }
+            let css = style.css();
+            html! {
+                <span
+                    class={classes!(style.classes())}
+                    style={(!css.is_empty()).then_some(css)}
+                >{run}</span>
             }
         })
-        .collect()
+    }</span> }
+}
+
+pub fn convert(err: &Error) -> Html {
+    ansi_to_html(&err.render().ansi().to_string())
+}
+
+#[test]
+fn test_parse_colors() {
+    let runs = parse("\u{1b}[31merror:\u{1b}[0m plain \u{1b}[1;92mbright\u{1b}[m");
+    assert_eq!(runs.len(), 3);
+    assert_eq!(runs[0].0.fg, Some(AnsiColor::Indexed(1)));
+    assert_eq!(runs[0].1, "error:");
+    assert_eq!(runs[1], (AnsiStyle::default(), " plain ".to_string()));
+    assert!(runs[2].0.bold);
+    assert_eq!(runs[2].0.fg, Some(AnsiColor::Indexed(10)));
+
+    let extended = parse("\u{1b}[38;5;208;48;2;10;20;30mx");
+    assert_eq!(extended[0].0.fg, Some(AnsiColor::Indexed(208)));
+    assert_eq!(extended[0].0.bg, Some(AnsiColor::Rgb(10, 20, 30)));
+    assert_eq!(
+        parse("\u{1b}[44;103mx")[0].0.bg,
+        Some(AnsiColor::Indexed(11))
+    );
+
+    // sub-parameters belong to the parameter before them, and the color space can be left empty
+    let colons = parse("\u{1b}[38:2::255:0:0;48:5:208;1mx\u{1b}[38:2:10:20:30;4:0my");
+    assert_eq!(colons[0].0.fg, Some(AnsiColor::Rgb(255, 0, 0)));
+    assert_eq!(colons[0].0.bg, Some(AnsiColor::Indexed(208)));
+    assert!(colons[0].0.bold);
+    assert_eq!(colons[1].0.fg, Some(AnsiColor::Rgb(10, 20, 30)));
+    assert!(!colons[1].0.underline);
+}
+
+#[test]
+fn test_parse_attributes() {
+    let runs = parse("\u{1b}[1;3;4;7ma\u{1b}[22;24mb\u{1b}[39;23;27mc");
+    let (a, b, c) = (runs[0].0, runs[1].0, runs[2].0);
+    assert!(a.bold && a.italic && a.underline && a.reverse);
+    assert!(!b.bold && b.italic && !b.underline && b.reverse);
+    assert_eq!(c, AnsiStyle::default());
+    assert_eq!(runs.len(), 3);
+}
+
+#[test]
+fn test_parse_other_sequences() {
+    // cursor movement and malformed colors don't leave anything behind
+    let runs = parse("a\u{1b}[2Kb\u{1b}[38;9mc\u{1b}cd");
+    assert_eq!(runs, [(AnsiStyle::default(), "abcd".to_string())]);
+    assert_eq!(AnsiColor::Indexed(196).css(), "rgb(255, 0, 0)");
+    assert_eq!(AnsiColor::Indexed(244).css(), "rgb(128, 128, 128)");
 }
@@ -1,66 +1,331 @@
 use std::ops::Not;
 
-use clap::{Command, CommandFactory};
+use clap::{Arg, Command};
 
-use crate::cli::Cli;
+use crate::cli::root_command;
+use crate::commands::REGISTRY;
+use crate::utils::alias_store::AliasStore;
+use crate::utils::fuzzy::fuzzy_match;
+use crate::utils::history_store::HistoryEntry;
+use crate::utils::keymap::Keymap;
+use crate::utils::variables::Variables;
 
-fn completions_for_command(cmd: &Command) -> Vec<(String, Option<String>)> {
+/// An option for completing the token being typed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    pub value: String,
+    pub description: Option<String>,
+    /// Indices of the characters in `value` which matched the token being typed
+    pub matched: Vec<usize>,
+}
+
+impl Completion {
+    pub fn new(value: String, description: Option<String>) -> Self {
+        Completion {
+            value,
+            description,
+            matched: Vec::new(),
+        }
+    }
+}
+
+/// The terminal's state, which some arguments complete their values from
+#[derive(Debug, Clone, Copy)]
+pub struct CompletionContext<'a> {
+    pub aliases: &'a AliasStore,
+    pub history: &'a [HistoryEntry],
+    pub variables: &'a Variables,
+    pub keymap: &'a Keymap,
+    /// The directory paths are completed from
+    pub cwd: &'a str,
+    /// Values already given to the argument being completed, when it takes more than one
+    pub given: &'a [&'a str],
+}
+
+fn completions_for_command(cmd: &Command) -> Vec<Completion> {
     let mut completions = Vec::new();
 
     // Subcommands
     completions.extend(cmd.get_subcommands().map(|sc| {
-        (
+        Completion::new(
             sc.get_name().to_string(),
             sc.get_about().map(ToString::to_string),
         )
     }));
 
-    // Flags
-    completions.extend(cmd.get_arguments().filter_map(|a| {
-        // Only include named flags (skip positional args)
+    completions.extend(completions_for_flags(cmd));
 
-        a.get_long()
-            .map(|name| (format!("--{}", name), a.get_help().map(ToString::to_string)))
-    }));
+    completions
+}
+
+fn completions_for_flags(cmd: &Command) -> Vec<Completion> {
+    let mut completions = Vec::new();
+    for a in cmd.get_arguments().filter(|a| !a.is_hide_set()) {
+        let help = a.get_help().map(ToString::to_string);
+        if let Some(name) = a.get_long() {
+            completions.push(Completion::new(format!("--{}", name), help.clone()));
+        }
+        if let Some(short) = a.get_short() {
+            completions.push(Completion::new(format!("-{}", short), help));
+        }
+    }
+    completions
+}
+
+/// Lists the values an argument accepts, from its possible values and from the terminal's state
+fn completions_for_value(path: &[&str], arg: &Arg, context: &CompletionContext) -> Vec<Completion> {
+    let mut completions: Vec<Completion> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| {
+            Completion::new(
+                value.get_name().to_string(),
+                value.get_help().map(ToString::to_string),
+            )
+        })
+        .collect();
+
+    if let Some(command) = path.first().and_then(|name| REGISTRY.get(name)) {
+        completions.extend(command.complete(path, arg, context));
+    }
 
     completions
 }
 
-pub fn get_autocomplete(input: String) -> Vec<(String, Option<String>)> {
-    let input = input.to_lowercase();
-    let cli = Cli::command();
+pub fn get_autocomplete(input: &str, context: &CompletionContext) -> Vec<Completion> {
+    let cli = root_command();
 
     let mut tokens: Vec<&str> = input.split_whitespace().collect();
     // the last token is popped from the list unless there's a space after it
     let last_token = input
-        .ends_with(' ')
+        .ends_with(char::is_whitespace)
         .not()
         .then(|| tokens.pop())
         .flatten()
         .unwrap_or("");
 
+    // complete the rest of an aliased command as if its expansion had been typed
+    let expansion = tokens.first().and_then(|first| context.aliases.get(first));
+    if let Some(expansion) = expansion {
+        tokens.splice(..1, expansion.split_whitespace());
+    }
+
     let mut current_cmd = &cli;
+    // the names of the subcommands followed so far
+    let mut path: Vec<&str> = Vec::new();
+    let mut positionals = 0;
+    // the values given so far to a positional which takes more than one
+    let mut given: Vec<&str> = Vec::new();
+    // a flag which is still waiting for its value
+    let mut pending: Option<&Arg> = None;
 
     // go through each token and follow the chain of subcommands
     for token in &tokens {
-        if let Some(sub) = current_cmd.find_subcommand(token) {
-            current_cmd = sub;
-        } else {
-            // if the token is a flag
-            let is_valid_flag = current_cmd
+        if pending.take().is_some() {
+            continue;
+        }
+
+        if let Some(flag) = token.strip_prefix("--") {
+            let (name, inline_value) = match flag.split_once('=') {
+                Some((name, _)) => (name, true),
+                None => (flag, false),
+            };
+            let Some(arg) = current_cmd
                 .get_arguments()
-                .any(|a| a.get_long().map(|l| format!("--{}", l)) == Some(token.to_string()));
-            if !is_valid_flag {
+                .find(|a| a.get_long() == Some(name))
+            else {
                 return vec![];
+            };
+            if !inline_value && arg.get_action().takes_values() {
+                pending = Some(arg);
+            }
+        } else if let Some(shorts) = token.strip_prefix('-').filter(|s| !s.is_empty()) {
+            // short flags can be grouped together, and the last one may take a value
+            for (i, short) in shorts.char_indices() {
+                let Some(arg) = current_cmd
+                    .get_arguments()
+                    .find(|a| a.get_short() == Some(short))
+                else {
+                    return vec![];
+                };
+                if arg.get_action().takes_values() {
+                    if i + short.len_utf8() == shorts.len() {
+                        pending = Some(arg);
+                    }
+                    break;
+                }
+            }
+        } else if let Some(sub) = current_cmd
+            .get_subcommands()
+            .find(|sc| positionals == 0 && sc.get_name().eq_ignore_ascii_case(token))
+        {
+            current_cmd = sub;
+            path.push(sub.get_name());
+        } else if let Some(arg) = current_cmd.get_positionals().nth(positionals) {
+            if arg
+                .get_num_args()
+                .is_some_and(|range| range.max_values() > 1)
+            {
+                given.push(token);
+            } else {
+                positionals += 1;
             }
+        } else {
+            return vec![];
         }
     }
 
-    let completions = completions_for_command(current_cmd);
+    let context = &CompletionContext {
+        given: &given,
+        ..*context
+    };
+    let mut completions = match pending {
+        Some(arg) => completions_for_value(&path, arg, context),
+        None => {
+            // values for the next positional come first, since they're usually what's expected
+            let mut completions = match current_cmd.get_positionals().nth(positionals) {
+                Some(arg) => completions_for_value(&path, arg, context),
+                None => Vec::new(),
+            };
+            if positionals == 0 && given.is_empty() {
+                completions.extend(completions_for_command(current_cmd));
+            } else {
+                completions.extend(completions_for_flags(current_cmd));
+            }
+            completions
+        }
+    };
+    if tokens.is_empty() {
+        completions.extend(context.aliases.aliases().map(|(name, expansion)| {
+            Completion::new(name.to_string(), Some(format!("alias for '{expansion}'")))
+        }));
+    }
 
-    // use the last token only to filter the list of options from the previous tokens
-    completions
+    // use the last token only to filter and rank the options from the previous tokens
+    let mut ranked: Vec<(i32, Completion)> = completions
+        .into_iter()
+        .filter_map(|mut completion| {
+            let found = fuzzy_match(last_token, &completion.value)?;
+            completion.matched = found.indices;
+            Some((found.score, completion))
+        })
+        .collect();
+    // the sort is stable, so equally good matches keep their original order
+    ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    ranked
         .into_iter()
-        .filter(|(c, _)| c.starts_with(last_token))
+        .map(|(_, completion)| completion)
         .collect()
 }
+
+/// Finds the longest prefix shared by every value
+pub fn common_prefix(values: &[String]) -> String {
+    let Some((first, rest)) = values.split_first() else {
+        return String::new();
+    };
+    let mut len = first.len();
+    for value in rest {
+        len = first
+            .char_indices()
+            .zip(value.chars())
+            .take_while(|((i, a), b)| *i < len && a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0);
+    }
+    first[..len].to_string()
+}
+
+#[cfg(test)]
+fn values(completions: &[Completion]) -> Vec<&str> {
+    completions.iter().map(|c| c.value.as_str()).collect()
+}
+
+#[cfg(test)]
+fn complete(input: &str, history: &[HistoryEntry]) -> Vec<Completion> {
+    let context = CompletionContext {
+        aliases: &AliasStore::default(),
+        history,
+        variables: &Variables::default(),
+        keymap: &Keymap::default(),
+        cwd: "/",
+        given: &[],
+    };
+    get_autocomplete(input, &context)
+}
+
+#[test]
+fn test_fuzzy_autocomplete() {
+    assert_eq!(values(&complete("prj", &[]))[0], "projects");
+    assert_eq!(values(&complete("cnw", &[]))[0], "conway");
+    let about = complete("conway --abt", &[]);
+    assert_eq!(values(&about)[0], "--about");
+    assert_eq!(about[0].matched, [0, 1, 2, 3, 6]);
+    assert!(complete("zzz", &[]).is_empty());
+}
+
+#[test]
+fn test_value_autocomplete() {
+    let presets = complete("conway --preset ", &[]);
+    assert_eq!(
+        values(&presets),
+        ["glider", "blinker", "pentadecathlon", "lwss"]
+    );
+    assert_eq!(values(&complete("conway -p bl", &[]))[0], "blinker");
+    // once the value is given, flags are offered again
+    assert!(values(&complete("conway --preset lwss ", &[])).contains(&"--width"));
+    assert!(values(&complete("conway ", &[])).contains(&"-a"));
+
+    let history = ["about", "conway --about"].map(|input| HistoryEntry {
+        input: input.to_string(),
+        time: None,
+        ok: true,
+    });
+    let indices = complete("history index ", &history);
+    assert_eq!(values(&indices)[..2], ["1", "0"]);
+    assert_eq!(indices[0].description.as_deref(), Some("conway --about"));
+    assert_eq!(values(&complete("help conw", &[]))[0], "conway");
+    // help offers the subcommands of the command named so far
+    let subcommands = complete("help history ", &[]);
+    assert!(
+        ["index", "search", "clear"]
+            .iter()
+            .all(|sc| values(&subcommands).contains(sc))
+    );
+    assert!(!values(&subcommands).contains(&"conway"));
+    assert_eq!(values(&complete("HELP history sea", &[]))[0], "search");
+    // no more positionals after the index
+    assert!(!values(&complete("history index 0 ", &history)).contains(&"0"));
+}
+
+#[test]
+fn test_path_autocomplete() {
+    let paths = complete("cd ", &[]);
+    assert_eq!(
+        values(&paths)[..3],
+        ["projects/", "projects/current/", "projects/web/"]
+    );
+    assert!(!values(&paths).contains(&"about.md"));
+    assert_eq!(
+        values(&complete("cat prcrs", &[]))[0],
+        "projects/prc/prc-rs.md"
+    );
+    assert_eq!(values(&complete("ls sk/lang", &[]))[0], "skills/languages");
+    let projects = complete("project portf", &[]);
+    assert_eq!(values(&projects)[0], "this-portfolio");
+    let tags = complete("projects --tag ", &[]);
+    assert_eq!(values(&tags)[0], "rust");
+    assert_eq!(values(&complete("projects --group p", &[]))[0], "prc");
+}
+
+#[test]
+fn test_common_prefix() {
+    let prefix =
+        |values: &[&str]| common_prefix(&values.iter().map(|v| v.to_string()).collect::<Vec<_>>());
+    assert_eq!(prefix(&["--preset", "--pretty"]), "--pre");
+    assert_eq!(prefix(&["history"]), "history");
+    assert_eq!(prefix(&["about", "alias", "conway"]), "");
+    assert_eq!(prefix(&["café", "cafés"]), "café");
+    assert_eq!(prefix(&[]), "");
+}
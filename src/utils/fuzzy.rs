@@ -0,0 +1,117 @@
+/// Score for each character matched
+const MATCH_SCORE: i32 = 16;
+/// Bonus for a match directly after the previous one
+const CONSECUTIVE_BONUS: i32 = 16;
+/// Bonus for a match at the start of a word, like the `p` in `--preset`
+const BOUNDARY_BONUS: i32 = 12;
+/// Penalty for each character skipped between matches
+const GAP_PENALTY: i32 = 2;
+
+/// How well a pattern matched a candidate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    /// Indices of the candidate's characters (not bytes) that matched the pattern
+    pub indices: Vec<usize>,
+}
+
+/// Matches a pattern against a candidate as a case-insensitive subsequence. Of all the ways the
+/// pattern can line up with the candidate, the one with the best score is chosen, favoring
+/// characters that are contiguous or at the start of words.
+pub fn fuzzy_match(pattern: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    let candidate: Vec<char> = candidate.chars().collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            indices: Vec::new(),
+        });
+    }
+    if pattern.len() > candidate.len() {
+        return None;
+    }
+
+    let bonus = |j: usize| -> i32 {
+        let boundary = j == 0
+            || matches!(candidate[j - 1], '-' | '_' | ' ' | '/' | '.' | '=')
+            || (candidate[j - 1].is_lowercase() && candidate[j].is_uppercase());
+        MATCH_SCORE + if boundary { BOUNDARY_BONUS } else { 0 }
+    };
+    let eq = |i: usize, j: usize| {
+        candidate[j]
+            .to_lowercase()
+            .eq(pattern[i..=i].iter().copied())
+    };
+
+    // scores[i][j] is the best score for the pattern up to `i`, with `i` matched at `j`, and
+    // previous[i][j] is where `i - 1` was matched to get it
+    let mut scores = vec![vec![None; candidate.len()]; pattern.len()];
+    let mut previous = vec![vec![0; candidate.len()]; pattern.len()];
+
+    for (j, score) in scores[0].iter_mut().enumerate() {
+        if eq(0, j) {
+            *score = Some(bonus(j) - GAP_PENALTY * j as i32);
+        }
+    }
+    for i in 1..pattern.len() {
+        for j in i..candidate.len() {
+            if !eq(i, j) {
+                continue;
+            }
+            let best = (i - 1..j)
+                .filter_map(|k| {
+                    let score = scores[i - 1][k]?;
+                    let link = if k + 1 == j {
+                        CONSECUTIVE_BONUS
+                    } else {
+                        -GAP_PENALTY * (j - k - 1) as i32
+                    };
+                    Some((score + link, k))
+                })
+                .max_by_key(|(score, _)| *score);
+            if let Some((score, k)) = best {
+                scores[i][j] = Some(score + bonus(j));
+                previous[i][j] = k;
+            }
+        }
+    }
+
+    let last = pattern.len() - 1;
+    let (mut j, score) = scores[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| Some((j, (*score)?)))
+        .max_by_key(|(_, score)| *score)?;
+
+    let mut indices = vec![0; pattern.len()];
+    for i in (0..pattern.len()).rev() {
+        indices[i] = j;
+        j = previous[i][j];
+    }
+
+    Some(FuzzyMatch { score, indices })
+}
+
+#[test]
+fn test_fuzzy_subsequence() {
+    assert_eq!(fuzzy_match("prj", "projects").unwrap().indices, [0, 1, 3]);
+    assert_eq!(fuzzy_match("cnw", "conway").unwrap().indices, [0, 2, 3]);
+    assert_eq!(fuzzy_match("CON", "conway").unwrap().indices, [0, 1, 2]);
+    assert!(fuzzy_match("xyz", "conway").is_none());
+    assert!(fuzzy_match("conways", "conway").is_none());
+    assert_eq!(fuzzy_match("", "conway").unwrap().score, 0);
+}
+
+#[test]
+fn test_fuzzy_prefers_contiguous_and_boundaries() {
+    // the contiguous `ab` beats the scattered one
+    assert_eq!(fuzzy_match("ab", "a_xab").unwrap().indices, [3, 4]);
+    // matching the start of `--preset` beats the `p` inside `--help`
+    let preset = fuzzy_match("p", "--preset").unwrap().score;
+    let help = fuzzy_match("p", "--help").unwrap().score;
+    assert!(preset > help);
+    // a prefix beats a scattered match
+    let history = fuzzy_match("hi", "history").unwrap().score;
+    let height = fuzzy_match("hi", "height").unwrap().score;
+    assert!(history > height);
+}
@@ -70,10 +70,7 @@ impl Game {
 
     /// The basic rule to Conway's version of the automata for cells
     fn alive(already_alive: bool, neighbors: usize) -> bool {
-        match (already_alive, neighbors) {
-            (_, 3) | (true, 2) => true,
-            _ => false,
-        }
+        matches!((already_alive, neighbors), (_, 3) | (true, 2))
     }
 
     fn update_cell(&mut self, coord: [usize; 2]) {
@@ -181,7 +178,7 @@ fn test_glider() {
     assert_eq!(game.front().row(3).to_slice(), Some([0, 1, 1, 1].as_ref()));
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GamePreset {
     pub width: usize,
     pub height: usize,
@@ -0,0 +1,165 @@
+//! Bash-style history expansion, which replaces `!!`, `!n`, `!-n` and `!prefix` in a line with
+//! earlier lines, and turns `^old^new^` into the last line with `old` replaced by `new`. Entries
+//! are numbered from 0, the same as in `history index`.
+
+/// A reference to an entry in the history, written after a `!`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Event<'a> {
+    /// `!!`, the last entry
+    Last,
+    /// `!n`, the entry at an index
+    Index(usize),
+    /// `!-n`, the entry `n` back from the end, where `!-1` is the last
+    Back(usize),
+    /// `!prefix`, the newest entry starting with a prefix
+    Prefix(&'a str),
+}
+
+/// Reads the event after a `!`, along with the length of the text it was written with. A `!` at
+/// the end of the line, or followed by a space, quote, `=`, bracket or operator, isn't an event.
+fn parse_event(rest: &str) -> Option<(Event<'_>, usize)> {
+    if rest.starts_with('!') {
+        return Some((Event::Last, 1));
+    }
+    let digits = |text: &str| {
+        text.find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len())
+    };
+    if let Some(back) = rest.strip_prefix('-') {
+        let len = digits(back);
+        let n = back[..len].parse().ok()?;
+        return Some((Event::Back(n), len + 1));
+    }
+    let len = digits(rest);
+    if len > 0 {
+        return Some((Event::Index(rest[..len].parse().ok()?), len));
+    }
+    let len = rest
+        .find(|c: char| c.is_whitespace() || "=();&|'\"".contains(c))
+        .unwrap_or(rest.len());
+    (len > 0).then(|| (Event::Prefix(&rest[..len]), len))
+}
+
+fn find_event<'a>(event: Event, history: &'a [impl AsRef<str>]) -> Option<&'a str> {
+    let entry = match event {
+        Event::Last => history.last(),
+        Event::Index(i) => history.get(i),
+        Event::Back(n) => history.len().checked_sub(n).and_then(|i| history.get(i)),
+        Event::Prefix(prefix) => history
+            .iter()
+            .rev()
+            .find(|entry| entry.as_ref().starts_with(prefix)),
+    };
+    entry.map(AsRef::as_ref)
+}
+
+/// Expands the references to history in a line, returning `None` when it has none. `!` isn't
+/// expanded inside single quotes or after a backslash.
+pub fn expand_history(line: &str, history: &[impl AsRef<str>]) -> Result<Option<String>, String> {
+    if let Some(rest) = line.strip_prefix('^') {
+        return quick_substitution(rest, history).map(Some);
+    }
+
+    let mut expanded = String::new();
+    let mut changed = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('\''), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (_, '\\') => {
+                expanded.push(c);
+                if let Some((_, escaped)) = chars.next() {
+                    expanded.push(escaped);
+                }
+                continue;
+            }
+            (_, '!') => {
+                if let Some((event, len)) = parse_event(&line[i + 1..]) {
+                    let entry = find_event(event, history).ok_or_else(|| {
+                        format!("!{}: event not found", &line[i + 1..i + 1 + len])
+                    })?;
+                    expanded.push_str(entry);
+                    changed = true;
+                    // the event's text is part of the `!` reference, so it's skipped
+                    for _ in line[i + 1..i + 1 + len].chars() {
+                        chars.next();
+                    }
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        expanded.push(c);
+    }
+    Ok(changed.then_some(expanded))
+}
+
+/// Runs `^old^new^rest` as the last line with the first `old` replaced by `new`, followed by
+/// `rest`. Both the last `^` and `new` can be left out.
+fn quick_substitution(rest: &str, history: &[impl AsRef<str>]) -> Result<String, String> {
+    let mut parts = rest.splitn(3, '^');
+    let old = parts.next().unwrap_or_default();
+    let new = parts.next().unwrap_or_default();
+    let tail = parts.next().unwrap_or_default();
+    let last = find_event(Event::Last, history)
+        .ok_or_else(|| format!("^{rest}: no previous line to substitute in"))?;
+    if old.is_empty() || !last.contains(old) {
+        return Err(format!("^{old}^{new}: substitution failed"));
+    }
+    Ok(format!("{}{tail}", last.replacen(old, new, 1)))
+}
+
+#[cfg(test)]
+const HISTORY: [&str; 4] = ["about", "conway --preset lwss", "diff a b", "cat about.md"];
+
+#[cfg(test)]
+fn expand(line: &str) -> Result<Option<String>, String> {
+    expand_history(line, &HISTORY)
+}
+
+#[test]
+fn test_event_designators() {
+    assert_eq!(expand("!!"), Ok(Some("cat about.md".into())));
+    assert_eq!(expand("!1"), Ok(Some("conway --preset lwss".into())));
+    assert_eq!(expand("!-2"), Ok(Some("diff a b".into())));
+    assert_eq!(
+        expand("!con --width 20"),
+        Ok(Some("conway --preset lwss --width 20".into()))
+    );
+    assert_eq!(expand("!! && !a"), Ok(Some("cat about.md && about".into())));
+    assert_eq!(expand("!9"), Err("!9: event not found".into()));
+    assert_eq!(expand("!-5"), Err("!-5: event not found".into()));
+    assert_eq!(expand("!nope"), Err("!nope: event not found".into()));
+    assert_eq!(
+        expand_history("!!", &[] as &[String]),
+        Err("!!: event not found".into())
+    );
+}
+
+#[test]
+fn test_literal_bangs() {
+    assert_eq!(expand("about"), Ok(None));
+    assert_eq!(expand("set X=hi!"), Ok(None));
+    assert_eq!(expand("diff 'a!!' b! c"), Ok(None));
+    assert_eq!(expand(r"diff \!! b"), Ok(None));
+    assert_eq!(expand(r#"project "This Portfolio!""#), Ok(None));
+    // double quotes don't stop expansion, like in bash
+    assert_eq!(
+        expand(r#"diff "!d" x"#),
+        Ok(Some(r#"diff "diff a b" x"#.into()))
+    );
+}
+
+#[test]
+fn test_quick_substitution() {
+    assert_eq!(expand("^about^contact^"), Ok(Some("cat contact.md".into())));
+    assert_eq!(expand("^about^contact"), Ok(Some("cat contact.md".into())));
+    assert_eq!(expand("^.md^^ extra"), Ok(Some("cat about extra".into())));
+    assert_eq!(
+        expand("^nope^x"),
+        Err("^nope^x: substitution failed".into())
+    );
+}
@@ -1,34 +1,438 @@
-use gloo::storage::{LocalStorage, Storage};
+use std::ops::Range;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(test)]
+use crate::utils::storage::MemoryStore;
+use crate::utils::storage::{self, Corrupt, KeyValueStore, LocalStore, Schema};
+
+const SCHEMA: Schema<Vec<HistoryEntry>> = Schema {
+    key: "portfolio.history",
+    version: 1,
+    // misspelled in the first version of the site
+    legacy_keys: &["portolio.history"],
+    migrate,
+};
+
+/// The most entries kept when `HISTSIZE` isn't set
+pub const DEFAULT_HISTSIZE: usize = 500;
+
+/// A line that was entered into the terminal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub input: String,
+    /// When the line was entered, in milliseconds since the unix epoch, which isn't known for
+    /// lines saved before times were recorded
+    pub time: Option<f64>,
+    /// Whether the line succeeded, which is the status of the last command it ran
+    pub ok: bool,
+}
+
+impl AsRef<str> for HistoryEntry {
+    fn as_ref(&self) -> &str {
+        &self.input
+    }
+}
+
+/// Which lines are recorded, like bash's `HISTSIZE` and `HISTCONTROL`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryOptions {
+    /// The most entries kept, where the oldest are removed first
+    pub max_size: usize,
+    /// Skips a line which is the same as the one before it
+    pub ignoredups: bool,
+    /// Skips a line which starts with a space
+    pub ignorespace: bool,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        HistoryOptions {
+            max_size: DEFAULT_HISTSIZE,
+            ignoredups: true,
+            ignorespace: false,
+        }
+    }
+}
+
+impl HistoryOptions {
+    /// Reads the options from the `HISTSIZE` and `HISTCONTROL` variables, where `HISTCONTROL` is
+    /// a list of `ignoredups`, `ignorespace` or `ignoreboth` separated by colons. Unset or invalid
+    /// variables leave the defaults.
+    pub fn from_variables<'a>(variable: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let mut options = HistoryOptions::default();
+        if let Some(size) = variable("HISTSIZE").and_then(|size| size.trim().parse().ok()) {
+            options.max_size = size;
+        }
+        if let Some(control) = variable("HISTCONTROL") {
+            options.ignoredups = false;
+            for value in control.split(':') {
+                match value.trim() {
+                    "ignoredups" => options.ignoredups = true,
+                    "ignorespace" => options.ignorespace = true,
+                    "ignoreboth" => {
+                        options.ignoredups = true;
+                        options.ignorespace = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        options
+    }
+}
 
-const HISTORY_KEY: &str = "portolio.history";
+/// A line saved before the history was versioned, which was only its input at first
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LegacyEntry {
+    Input(String),
+    Entry { input: String, time: f64, ok: bool },
+}
+
+#[derive(Debug, Deserialize)]
+struct LegacyHistory {
+    history: Vec<LegacyEntry>,
+}
+
+fn migrate(version: u32, data: Value) -> Result<Vec<HistoryEntry>, String> {
+    if version != 0 {
+        return Err(format!("it has an unknown version ({version})"));
+    }
+    let legacy: LegacyHistory = serde_json::from_value(data).map_err(storage::mismatch)?;
+    let entries = legacy.history.into_iter().map(|entry| match entry {
+        LegacyEntry::Input(input) => HistoryEntry {
+            input,
+            time: None,
+            ok: true,
+        },
+        LegacyEntry::Entry { input, time, ok } => HistoryEntry {
+            input,
+            time: Some(time),
+            ok,
+        },
+    });
+    Ok(entries.collect())
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct HistoryStore {
-    history: Vec<String>,
+/// The lines entered into the terminal, saved in a [`KeyValueStore`] which is LocalStorage by
+/// default
+#[derive(Debug, Clone, Default)]
+pub struct HistoryStore<S = LocalStore> {
+    store: S,
+    history: Vec<HistoryEntry>,
+    /// The saved history, if it couldn't be read. Nothing is saved while it's set, so it isn't
+    /// lost before it can be backed up.
+    corrupt: Option<Corrupt>,
+    /// Where the saved history was copied to when it was last backed up
+    backup_key: Option<String>,
 }
 
 impl HistoryStore {
     pub fn new() -> Self {
-        if let Ok(store) = LocalStorage::get::<HistoryStore>(HISTORY_KEY) {
-            return store;
-        }
+        HistoryStore::load(LocalStore)
+    }
+}
+
+impl<S: KeyValueStore> HistoryStore<S> {
+    /// Reads the history saved in a store, which it's saved back to as it changes
+    pub fn load(mut store: S) -> Self {
+        let (history, corrupt) = match SCHEMA.load(&mut store) {
+            Ok(history) => (history.unwrap_or_default(), None),
+            Err(corrupt) => (Vec::new(), Some(corrupt)),
+        };
         HistoryStore {
-            history: Vec::new(),
+            store,
+            history,
+            corrupt,
+            backup_key: None,
         }
     }
 
-    pub fn history(&self) -> Vec<String> {
-        self.history.clone()
+    pub fn corrupt(&self) -> Option<&Corrupt> {
+        self.corrupt.as_ref()
     }
 
-    pub fn push(&mut self, item: String) {
-        self.history.push(item);
-        let _ = LocalStorage::set(HISTORY_KEY, self);
+    pub fn backup_key(&self) -> Option<&str> {
+        self.backup_key.as_deref()
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.history
+    }
+
+    /// Records an entry unless the options skip it, removing the oldest entries to stay within
+    /// the maximum size. Returns whether it was recorded.
+    pub fn push(&mut self, entry: HistoryEntry, options: HistoryOptions) -> bool {
+        let recorded = self.record(entry, options);
+        self.save();
+        recorded
+    }
+
+    fn record(&mut self, entry: HistoryEntry, options: HistoryOptions) -> bool {
+        let skip = entry.input.trim().is_empty()
+            || (options.ignorespace && entry.input.starts_with(' '))
+            || (options.ignoredups
+                && self
+                    .history
+                    .last()
+                    .is_some_and(|last| last.input == entry.input));
+        if !skip {
+            self.history.push(entry);
+        }
+        let excess = self.history.len().saturating_sub(options.max_size);
+        self.history.drain(..excess);
+        !skip
+    }
+
+    /// Removes the entry at an index, returning it if it existed
+    pub fn delete(&mut self, index: usize) -> Option<HistoryEntry> {
+        if index >= self.history.len() {
+            return None;
+        }
+        let removed = self.history.remove(index);
+        self.save();
+        Some(removed)
     }
 
+    /// Removes every entry, replacing the saved history even if it couldn't be read
     pub fn clear(&mut self) {
         self.history = Vec::new();
-        let _ = LocalStorage::set(HISTORY_KEY, self);
+        if let Some(corrupt) = self.corrupt.take() {
+            storage::reset(&mut self.store, &corrupt);
+        }
+        self.save();
+    }
+
+    /// Copies the saved history which couldn't be read to a key of its own, then replaces it with
+    /// this session's entries. Returns whether there was anything to back up.
+    pub fn back_up(&mut self) -> bool {
+        let Some(corrupt) = self.corrupt.take() else {
+            return false;
+        };
+        self.backup_key = Some(storage::back_up(&mut self.store, &corrupt));
+        self.save();
+        true
+    }
+
+    fn save(&mut self) {
+        if self.corrupt.is_none() {
+            SCHEMA.save(&mut self.store, &self.history);
+        }
+    }
+}
+
+/// A range of entries, written like a range in rust: `2..5`, `2..=4`, `3..` or `..5`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryRange {
+    start: usize,
+    /// The index after the last entry in the range, or `None` to go up to the newest entry
+    end: Option<usize>,
+}
+
+impl FromStr for EntryRange {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{text}' isn't a range like 2..5, 2..=4, 3.. or ..5");
+        let (start, end) = text.split_once("..").ok_or_else(invalid)?;
+        let index = |text: &str| text.parse::<usize>().map_err(|_| invalid());
+        let start = if start.is_empty() { 0 } else { index(start)? };
+        let end = match end.strip_prefix('=') {
+            Some(last) => Some(index(last)? + 1),
+            None if end.is_empty() => None,
+            None => Some(index(end)?),
+        };
+        Ok(EntryRange { start, end })
+    }
+}
+
+impl EntryRange {
+    /// Gets the indices in the range, limited to a history with `len` entries
+    pub fn indices(self, len: usize) -> Range<usize> {
+        let end = self.end.unwrap_or(len).min(len);
+        self.start.min(end)..end
+    }
+}
+
+/// Describes how long ago a time was, like `5 minutes ago`, with both times in milliseconds
+pub fn relative_time(time: f64, now: f64) -> String {
+    let seconds = ((now - time) / 1000.0).max(0.0) as u64;
+    let (count, unit) = match seconds {
+        0..10 => return "just now".to_string(),
+        10..60 => (seconds, "second"),
+        60..3600 => (seconds / 60, "minute"),
+        3600..86400 => (seconds / 3600, "hour"),
+        86400..2592000 => (seconds / 86400, "day"),
+        2592000..31536000 => (seconds / 2592000, "month"),
+        _ => (seconds / 31536000, "year"),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{count} {unit}{plural} ago")
+}
+
+#[cfg(test)]
+fn entry(input: &str) -> HistoryEntry {
+    HistoryEntry {
+        input: input.to_string(),
+        time: None,
+        ok: true,
     }
 }
+
+#[cfg(test)]
+fn inputs(store: &HistoryStore<MemoryStore>) -> Vec<&str> {
+    store.entries().iter().map(AsRef::as_ref).collect()
+}
+
+#[test]
+fn test_history_options() {
+    let mut store = HistoryStore::load(MemoryStore::default());
+    let options = HistoryOptions::default();
+    assert!(store.record(entry("about"), options));
+    assert!(!store.record(entry("about"), options));
+    assert!(!store.record(entry("   "), options));
+    assert!(store.record(entry(" skills"), options));
+    assert_eq!(inputs(&store), ["about", " skills"]);
+
+    let both =
+        HistoryOptions::from_variables(|name| (name == "HISTCONTROL").then_some("ignoreboth"));
+    assert!(!store.record(entry(" contact"), both));
+    let none = HistoryOptions::from_variables(|name| (name == "HISTCONTROL").then_some(""));
+    assert!(store.record(entry(" skills"), none));
+    assert_eq!(inputs(&store), ["about", " skills", " skills"]);
+}
+
+#[test]
+fn test_history_max_size() {
+    let mut store = HistoryStore::load(MemoryStore::default());
+    let options = HistoryOptions::from_variables(|name| (name == "HISTSIZE").then_some("2"));
+    assert_eq!(options.max_size, 2);
+    for input in ["about", "skills", "contact"] {
+        store.record(entry(input), options);
+    }
+    assert_eq!(inputs(&store), ["skills", "contact"]);
+    // a smaller size removes the oldest entries, even when the new line is skipped
+    store.record(
+        entry("contact"),
+        HistoryOptions {
+            max_size: 1,
+            ..options
+        },
+    );
+    assert_eq!(inputs(&store), ["contact"]);
+}
+
+#[test]
+fn test_relative_time() {
+    let now = 1_700_000_000_000.0;
+    assert_eq!(relative_time(now - 3_000.0, now), "just now");
+    assert_eq!(relative_time(now - 45_000.0, now), "45 seconds ago");
+    assert_eq!(relative_time(now - 60_000.0, now), "1 minute ago");
+    assert_eq!(relative_time(now - 7_200_000.0, now), "2 hours ago");
+    assert_eq!(relative_time(now - 86_400_000.0 * 3.0, now), "3 days ago");
+    assert_eq!(relative_time(now + 5_000.0, now), "just now");
+}
+
+#[test]
+fn test_history_migration() {
+    let current = r#"{"version":1,"data":[{"input":"about","time":5.0,"ok":false}]}"#;
+    assert_eq!(
+        SCHEMA.decode(current),
+        Ok(vec![HistoryEntry {
+            input: "about".to_string(),
+            time: Some(5.0),
+            ok: false,
+        }])
+    );
+    // the first shape, with only the inputs
+    let inputs = r#"{"history":["about","skills"]}"#;
+    assert_eq!(SCHEMA.decode(inputs).unwrap()[1], entry("skills"));
+    let entries = r#"{"history":[{"input":"about","time":5.0,"ok":true}]}"#;
+    assert_eq!(SCHEMA.decode(entries).unwrap()[0].time, Some(5.0));
+
+    assert!(
+        SCHEMA
+            .decode("[1, 2")
+            .unwrap_err()
+            .starts_with("it isn't valid JSON")
+    );
+    assert!(SCHEMA.decode(r#"{"history":[1]}"#).is_err());
+    assert!(SCHEMA.decode(r#"{"version":1,"data":{}}"#).is_err());
+    assert_eq!(
+        SCHEMA.decode(r#"{"version":2,"data":[]}"#),
+        Err("it was saved by a newer version of the site (version 2)".to_string())
+    );
+}
+
+#[test]
+fn test_history_storage() {
+    let mut memory = MemoryStore::default();
+    memory.set("portolio.history", r#"{"history":["about","skills"]}"#);
+    let mut store = HistoryStore::load(memory);
+    store.push(entry("contact"), HistoryOptions::default());
+    // moved over from the misspelled key, and saved with the new entry
+    assert_eq!(store.store.get("portolio.history"), None);
+    let reloaded = HistoryStore::load(store.store.clone());
+    assert_eq!(inputs(&reloaded), ["about", "skills", "contact"]);
+    assert_eq!(
+        store.delete(1).map(|entry| entry.input),
+        Some("skills".to_string())
+    );
+    assert_eq!(store.delete(5), None);
+    let reloaded = HistoryStore::load(store.store.clone());
+    assert_eq!(inputs(&reloaded), ["about", "contact"]);
+
+    let mut memory = MemoryStore::default();
+    memory.set("portfolio.history", "{not json");
+    let mut store = HistoryStore::load(memory);
+    assert!(store.corrupt().is_some());
+    // nothing replaces the unreadable history until it's backed up
+    store.push(entry("about"), HistoryOptions::default());
+    assert_eq!(
+        store.store.get("portfolio.history").as_deref(),
+        Some("{not json")
+    );
+    assert!(store.back_up());
+    assert_eq!(store.backup_key(), Some("portfolio.history.backup"));
+    assert_eq!(
+        store.store.get("portfolio.history.backup").as_deref(),
+        Some("{not json")
+    );
+    assert_eq!(inputs(&HistoryStore::load(store.store.clone())), ["about"]);
+    assert!(!store.back_up());
+
+    // a second backup doesn't replace the first
+    let mut memory = store.store.clone();
+    memory.set("portfolio.history", "[oops");
+    let mut store = HistoryStore::load(memory);
+    assert!(store.back_up());
+    assert_eq!(store.backup_key(), Some("portfolio.history.backup.2"));
+    assert_eq!(
+        store.store.get("portfolio.history.backup").as_deref(),
+        Some("{not json")
+    );
+    assert_eq!(
+        store.store.get("portfolio.history.backup.2").as_deref(),
+        Some("[oops")
+    );
+}
+
+#[test]
+fn test_entry_range() {
+    let range = |text: &str| text.parse::<EntryRange>().map(|range| range.indices(6));
+    assert_eq!(range("2..5"), Ok(2..5));
+    assert_eq!(range("2..=4"), Ok(2..5));
+    assert_eq!(range("3.."), Ok(3..6));
+    assert_eq!(range("..2"), Ok(0..2));
+    assert_eq!(range(".."), Ok(0..6));
+    // limited to the entries there are
+    assert_eq!(range("4..10"), Ok(4..6));
+    assert_eq!(range("8..10"), Ok(6..6));
+    assert_eq!(range("5..2"), Ok(2..2));
+    assert!(range("2").is_err());
+    assert!(range("a..b").is_err());
+    assert!(range("1..=").is_err());
+}
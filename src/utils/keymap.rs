@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+use crate::utils::storage::MemoryStore;
+use crate::utils::storage::{KeyValueStore, LocalStore};
+
+const KEYMAP_KEY: &str = "portfolio.keymap";
+
+/// Something the console does when a key is pressed. The names follow readline's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyAction {
+    #[value(help = "Move to the start of the line")]
+    BeginningOfLine,
+    #[value(help = "Move to the end of the line")]
+    EndOfLine,
+    #[value(help = "Move back to the start of a word")]
+    BackwardWord,
+    #[value(help = "Move forward to the end of a word")]
+    ForwardWord,
+    #[value(help = "Cut the word before the cursor, up to the previous space")]
+    UnixWordRubout,
+    #[value(help = "Cut everything before the cursor")]
+    UnixLineDiscard,
+    #[value(help = "Cut everything after the cursor")]
+    KillLine,
+    #[value(help = "Paste the text that was cut last")]
+    Yank,
+    #[value(help = "Clear the terminal's output")]
+    ClearScreen,
+    #[value(help = "Cancel the line being typed and stop anything running")]
+    Cancel,
+    #[value(help = "Search back through history")]
+    ReverseSearchHistory,
+}
+
+impl fmt::Display for KeyAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => f.write_str(value.get_name()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A key pressed along with any modifiers, written like `Ctrl-A` or `Alt-F`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyChord {
+    pub ctrl: bool,
+    pub alt: bool,
+    /// The key itself, in lowercase for letters
+    pub key: String,
+}
+
+impl KeyChord {
+    /// Builds a chord from a key event's fields. Letters are read from `code` when it's given,
+    /// since `key` changes with the keyboard layout and with Alt held on some systems.
+    pub fn from_event(key: &str, code: &str, ctrl: bool, alt: bool) -> Self {
+        let key = match code.strip_prefix("Key") {
+            Some(letter) if letter.len() == 1 => letter.to_lowercase(),
+            _ if key.chars().count() == 1 => key.to_lowercase(),
+            _ => key.to_string(),
+        };
+        KeyChord { ctrl, alt, key }
+    }
+
+    /// Reads a chord like `Ctrl-A`, `alt-f` or readline's `C-a` and `M-f`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut chord = KeyChord {
+            ctrl: false,
+            alt: false,
+            key: String::new(),
+        };
+        let mut rest = text.trim();
+        while let Some((modifier, tail)) = rest.split_once('-').filter(|(_, tail)| !tail.is_empty())
+        {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" | "c" => chord.ctrl = true,
+                "alt" | "meta" | "m" => chord.alt = true,
+                _ => return Err(format!("unknown modifier '{modifier}' in '{text}'")),
+            }
+            rest = tail;
+        }
+        if rest.is_empty() {
+            return Err(format!("no key given in '{text}'"));
+        }
+        chord.key = if rest.chars().count() == 1 {
+            rest.to_lowercase()
+        } else {
+            rest.to_string()
+        };
+        Ok(chord)
+    }
+
+    /// Checks that the chord can be bound. Keys used for typing, like letters, Enter and
+    /// Backspace, can only be bound along with Ctrl or Alt, so a binding can't stop the console
+    /// from being typed in.
+    pub fn check_bindable(&self) -> Result<(), String> {
+        let typing = self.key.chars().count() == 1
+            || ["Enter", "Backspace"]
+                .iter()
+                .any(|key| self.key.eq_ignore_ascii_case(key));
+        if typing && !self.ctrl && !self.alt {
+            return Err(format!(
+                "'{self}' is used for typing, so it can only be bound along with Ctrl or Alt"
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl-")?;
+        }
+        if self.alt {
+            f.write_str("Alt-")?;
+        }
+        if self.key.chars().count() == 1 {
+            f.write_str(&self.key.to_uppercase())
+        } else {
+            f.write_str(&self.key)
+        }
+    }
+}
+
+/// The shape bindings are saved in
+#[derive(Serialize, Deserialize)]
+struct SavedBindings<T> {
+    bindings: T,
+}
+
+/// Which action each key chord runs in the console, which can be changed with `bind`. Changes
+/// are saved in a [`KeyValueStore`], which is LocalStorage by default.
+#[derive(Debug, Clone)]
+pub struct Keymap<S = LocalStore> {
+    store: S,
+    /// Chords are kept as text, so they can be stored as keys
+    bindings: BTreeMap<String, KeyAction>,
+}
+
+impl<S: Default> Default for Keymap<S> {
+    fn default() -> Self {
+        Keymap {
+            store: S::default(),
+            bindings: default_bindings(),
+        }
+    }
+}
+
+fn default_bindings() -> BTreeMap<String, KeyAction> {
+    [
+        ("Ctrl-A", KeyAction::BeginningOfLine),
+        ("Ctrl-E", KeyAction::EndOfLine),
+        ("Alt-B", KeyAction::BackwardWord),
+        ("Alt-F", KeyAction::ForwardWord),
+        ("Ctrl-W", KeyAction::UnixWordRubout),
+        ("Ctrl-U", KeyAction::UnixLineDiscard),
+        ("Ctrl-K", KeyAction::KillLine),
+        ("Ctrl-Y", KeyAction::Yank),
+        ("Ctrl-L", KeyAction::ClearScreen),
+        ("Ctrl-C", KeyAction::Cancel),
+        ("Ctrl-R", KeyAction::ReverseSearchHistory),
+    ]
+    .into_iter()
+    .map(|(chord, action)| (chord.to_string(), action))
+    .collect()
+}
+
+impl Keymap {
+    pub fn new() -> Self {
+        Keymap::load(LocalStore)
+    }
+}
+
+impl<S: KeyValueStore> Keymap<S> {
+    /// Reads the bindings saved in a store, or the default ones if none are saved. Changes are
+    /// saved back to the store.
+    pub fn load(store: S) -> Self {
+        let mut bindings = store
+            .get_json::<SavedBindings<_>>(KEYMAP_KEY)
+            .map(|saved| saved.bindings)
+            .unwrap_or_else(default_bindings);
+        // drop any bindings saved before they were checked, which could block typing
+        bindings.retain(|chord, _| {
+            KeyChord::parse(chord).is_ok_and(|chord| chord.check_bindable().is_ok())
+        });
+        Keymap { store, bindings }
+    }
+
+    pub fn get(&self, chord: &KeyChord) -> Option<KeyAction> {
+        self.bindings.get(&chord.to_string()).copied()
+    }
+
+    pub fn bindings(&self) -> impl Iterator<Item = (&str, KeyAction)> {
+        self.bindings
+            .iter()
+            .map(|(chord, action)| (chord.as_str(), *action))
+    }
+
+    pub fn bind(&mut self, chord: &KeyChord, action: KeyAction) {
+        self.bindings.insert(chord.to_string(), action);
+        self.save();
+    }
+
+    /// Removes a binding, returning the action it ran if it existed
+    pub fn unbind(&mut self, chord: &KeyChord) -> Option<KeyAction> {
+        let removed = self.bindings.remove(&chord.to_string());
+        if removed.is_some() {
+            self.save();
+        }
+        removed
+    }
+
+    pub fn reset(&mut self) {
+        self.bindings = default_bindings();
+        self.store.delete(KEYMAP_KEY);
+    }
+
+    fn save(&mut self) {
+        let saved = SavedBindings {
+            bindings: &self.bindings,
+        };
+        self.store.set_json(KEYMAP_KEY, &saved);
+    }
+}
+
+#[test]
+fn test_parse_chord() {
+    let ctrl_a = KeyChord::parse("ctrl-a").unwrap();
+    assert!(ctrl_a.ctrl && !ctrl_a.alt);
+    assert_eq!(ctrl_a.to_string(), "Ctrl-A");
+    assert_eq!(KeyChord::parse("M-f").unwrap().to_string(), "Alt-F");
+    assert_eq!(KeyChord::parse("C-M-x").unwrap().to_string(), "Ctrl-Alt-X");
+    assert_eq!(KeyChord::parse("Ctrl--").unwrap().key, "-");
+    assert_eq!(KeyChord::parse("F2").unwrap().to_string(), "F2");
+    assert!(KeyChord::parse("Shift-A").is_err());
+    assert!(KeyChord::parse("").is_err());
+}
+
+#[test]
+fn test_bindable_chords() {
+    for chord in ["a", "Enter", "enter", "Backspace", "-"] {
+        let err = KeyChord::parse(chord)
+            .unwrap()
+            .check_bindable()
+            .unwrap_err();
+        assert!(
+            err.ends_with("only be bound along with Ctrl or Alt"),
+            "{err}"
+        );
+    }
+    for chord in ["Ctrl-A", "Alt-Enter", "Ctrl-Backspace", "F2", "ArrowUp"] {
+        assert_eq!(KeyChord::parse(chord).unwrap().check_bindable(), Ok(()));
+    }
+}
+
+#[test]
+fn test_chord_from_event() {
+    let keymap = Keymap::<MemoryStore>::default();
+    // Alt-B types a symbol on some layouts, but the key's code stays the same
+    let alt_b = KeyChord::from_event("∫", "KeyB", false, true);
+    assert_eq!(keymap.get(&alt_b), Some(KeyAction::BackwardWord));
+    let ctrl_c = KeyChord::from_event("C", "KeyC", true, false);
+    assert_eq!(keymap.get(&ctrl_c), Some(KeyAction::Cancel));
+    assert_eq!(
+        keymap.get(&KeyChord::from_event("a", "KeyA", false, false)),
+        None
+    );
+    assert_eq!(
+        KeyChord::from_event("Enter", "Enter", true, false).to_string(),
+        "Ctrl-Enter"
+    );
+}
+
+#[test]
+fn test_saved_keymap() {
+    let ctrl_x = KeyChord::parse("Ctrl-X").unwrap();
+    let ctrl_a = KeyChord::parse("Ctrl-A").unwrap();
+    let mut keymap = Keymap::load(MemoryStore::default());
+    keymap.bind(&ctrl_x, KeyAction::ClearScreen);
+    keymap.unbind(&ctrl_a);
+
+    // the changes are read back from the store
+    let mut saved = Keymap::load(keymap.store.clone());
+    assert_eq!(saved.get(&ctrl_x), Some(KeyAction::ClearScreen));
+    assert_eq!(saved.get(&ctrl_a), None);
+
+    // bindings which would block typing are dropped when they're read
+    let mut store = MemoryStore::default();
+    store.set(KEYMAP_KEY, r#"{"bindings":{"A":"yank","Ctrl-Y":"yank"}}"#);
+    let keymap = Keymap::load(store);
+    assert_eq!(
+        keymap.bindings().collect::<Vec<_>>(),
+        [("Ctrl-Y", KeyAction::Yank)]
+    );
+
+    saved.reset();
+    assert_eq!(saved.store.get(KEYMAP_KEY), None);
+    assert_eq!(saved.get(&ctrl_a), Some(KeyAction::BeginningOfLine));
+}
@@ -0,0 +1,153 @@
+//! Readline-style editing of the console's line. Cursors here are byte indices into the line,
+//! while the browser counts in UTF-16 code units, so there are conversions for both.
+
+use crate::utils::keymap::KeyAction;
+
+/// Applies an editing action to a line, moving the cursor and cutting or pasting text with
+/// `killed`. Returns whether the line's text changed. Actions which don't edit the line, like
+/// `clear-screen`, do nothing here.
+pub fn apply(
+    action: KeyAction,
+    line: &mut String,
+    cursor: &mut usize,
+    killed: &mut String,
+) -> bool {
+    *cursor = (*cursor).min(line.len());
+    match action {
+        KeyAction::BeginningOfLine => *cursor = 0,
+        KeyAction::EndOfLine => *cursor = line.len(),
+        KeyAction::BackwardWord => *cursor = word_start(line, *cursor, char::is_alphanumeric),
+        KeyAction::ForwardWord => *cursor = word_end(line, *cursor),
+        KeyAction::UnixWordRubout => {
+            let start = word_start(line, *cursor, |c| !c.is_whitespace());
+            return kill(line, start..*cursor, cursor, killed);
+        }
+        KeyAction::UnixLineDiscard => return kill(line, 0..*cursor, cursor, killed),
+        KeyAction::KillLine => {
+            let end = line.len();
+            return kill(line, *cursor..end, cursor, killed);
+        }
+        KeyAction::Yank => {
+            if killed.is_empty() {
+                return false;
+            }
+            line.insert_str(*cursor, killed);
+            *cursor += killed.len();
+            return true;
+        }
+        KeyAction::ClearScreen | KeyAction::Cancel | KeyAction::ReverseSearchHistory => {}
+    }
+    false
+}
+
+/// Cuts a range out of the line, leaving the cursor where it was taken from
+fn kill(
+    line: &mut String,
+    range: std::ops::Range<usize>,
+    cursor: &mut usize,
+    killed: &mut String,
+) -> bool {
+    if range.is_empty() {
+        return false;
+    }
+    *cursor = range.start;
+    *killed = line.drain(range).collect();
+    true
+}
+
+/// Finds the start of the word before the cursor, skipping anything that isn't part of a word
+/// first
+fn word_start(line: &str, cursor: usize, is_word: impl Fn(char) -> bool) -> usize {
+    line[..cursor]
+        .char_indices()
+        .rev()
+        .skip_while(|(_, c)| !is_word(*c))
+        .take_while(|(_, c)| is_word(*c))
+        .last()
+        .map_or(0, |(i, _)| i)
+}
+
+/// Finds the end of the word after the cursor, skipping anything that isn't part of a word first
+fn word_end(line: &str, cursor: usize) -> usize {
+    let mut chars = line[cursor..].char_indices().peekable();
+    while chars.next_if(|(_, c)| !c.is_alphanumeric()).is_some() {}
+    while chars.next_if(|(_, c)| c.is_alphanumeric()).is_some() {}
+    chars.peek().map_or(line.len(), |(i, _)| cursor + i)
+}
+
+/// Converts a cursor from the browser, counted in UTF-16 code units, to a byte index
+pub fn utf16_to_byte(line: &str, cursor: u32) -> usize {
+    let mut units = 0;
+    for (i, c) in line.char_indices() {
+        if units >= cursor as usize {
+            return i;
+        }
+        units += c.len_utf16();
+    }
+    line.len()
+}
+
+/// Converts a byte index to a cursor for the browser, counted in UTF-16 code units
+pub fn byte_to_utf16(line: &str, cursor: usize) -> u32 {
+    line[..cursor.min(line.len())].encode_utf16().count() as u32
+}
+
+#[cfg(test)]
+fn edit(action: KeyAction, line: &str, cursor: usize, killed: &mut String) -> (String, usize) {
+    let mut line = line.to_string();
+    let mut cursor = cursor;
+    apply(action, &mut line, &mut cursor, killed);
+    (line, cursor)
+}
+
+#[test]
+fn test_motion() {
+    let killed = &mut String::new();
+    let line = "conway --preset lwss";
+    assert_eq!(edit(KeyAction::BeginningOfLine, line, 9, killed).1, 0);
+    assert_eq!(edit(KeyAction::EndOfLine, line, 0, killed).1, 20);
+    // from inside `preset`, back to its start, then back again to the start of `conway`
+    assert_eq!(edit(KeyAction::BackwardWord, line, 12, killed).1, 9);
+    assert_eq!(edit(KeyAction::BackwardWord, line, 9, killed).1, 0);
+    assert_eq!(edit(KeyAction::ForwardWord, line, 0, killed).1, 6);
+    assert_eq!(edit(KeyAction::ForwardWord, line, 6, killed).1, 15);
+    assert_eq!(edit(KeyAction::ForwardWord, line, 16, killed).1, 20);
+    assert_eq!(edit(KeyAction::BackwardWord, "  ", 2, killed).1, 0);
+}
+
+#[test]
+fn test_kill_and_yank() {
+    let killed = &mut String::new();
+    let line = "conway --preset lwss";
+    assert_eq!(
+        edit(KeyAction::UnixWordRubout, line, 15, killed),
+        ("conway  lwss".to_string(), 7)
+    );
+    assert_eq!(killed, "--preset");
+    assert_eq!(
+        edit(KeyAction::Yank, "conway ", 7, killed),
+        ("conway --preset".to_string(), 15)
+    );
+    assert_eq!(
+        edit(KeyAction::UnixLineDiscard, line, 7, killed),
+        ("--preset lwss".to_string(), 0)
+    );
+    assert_eq!(killed, "conway ");
+    assert_eq!(
+        edit(KeyAction::KillLine, line, 6, killed),
+        ("conway".to_string(), 6)
+    );
+    assert_eq!(killed, " --preset lwss");
+    // nothing to cut leaves the last cut text alone
+    edit(KeyAction::KillLine, line, 20, killed);
+    assert_eq!(killed, " --preset lwss");
+}
+
+#[test]
+fn test_utf16_cursor() {
+    let line = "diff 🦀 é";
+    assert_eq!(utf16_to_byte(line, 7), 9);
+    assert_eq!(byte_to_utf16(line, 9), 7);
+    assert_eq!(utf16_to_byte(line, 100), line.len());
+    assert_eq!(byte_to_utf16(line, line.len()), 9);
+}
@@ -1,5 +1,19 @@
+pub mod alias_store;
 pub mod ansi_html;
 pub mod autocomplete;
 pub mod diff;
+pub mod fuzzy;
 pub mod game;
+pub mod history_expansion;
 pub mod history_store;
+pub mod keymap;
+pub mod line_edit;
+pub mod recall;
+pub mod route;
+pub mod search;
+pub mod startup_script;
+pub mod storage;
+pub mod tokenize;
+pub mod transcript;
+pub mod variables;
+pub mod vfs;
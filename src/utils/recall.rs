@@ -0,0 +1,140 @@
+/// Tracks the position while stepping through history with the arrow keys. The line being
+/// written before browsing started is kept as a draft, and comes back after stepping past the
+/// newest entry.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryCursor {
+    /// The entry currently shown, or `None` when the draft is shown
+    index: Option<usize>,
+    draft: String,
+}
+
+impl HistoryCursor {
+    /// Steps to the next older entry, returning the line to show
+    pub fn older(&mut self, history: &[impl AsRef<str>], current: &str) -> Option<String> {
+        let index = match self.index {
+            None if history.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                history.len() - 1
+            }
+            Some(0) => return None,
+            Some(i) => i - 1,
+        };
+        self.index = Some(index);
+        history.get(index).map(|item| item.as_ref().to_string())
+    }
+
+    /// Steps to the next newer entry, returning the line to show
+    pub fn newer(&mut self, history: &[impl AsRef<str>]) -> Option<String> {
+        let index = self.index?;
+        if index + 1 < history.len() {
+            self.index = Some(index + 1);
+            history.get(index + 1).map(|item| item.as_ref().to_string())
+        } else {
+            self.index = None;
+            Some(std::mem::take(&mut self.draft))
+        }
+    }
+
+    /// Stops browsing, discarding the draft
+    pub fn reset(&mut self) {
+        self.index = None;
+        self.draft.clear();
+    }
+}
+
+/// An incremental search backwards through history, like bash's `reverse-i-search`
+#[derive(Debug, Clone, Default)]
+pub struct ReverseSearch {
+    pub query: String,
+    /// The entry matching the query, if any
+    index: Option<usize>,
+    /// The line being written before searching started, restored if the search is cancelled
+    pub original: String,
+}
+
+impl ReverseSearch {
+    pub fn new(original: String) -> Self {
+        ReverseSearch {
+            original,
+            ..Default::default()
+        }
+    }
+
+    /// Updates the query, looking for the newest entry that contains it
+    pub fn set_query(&mut self, query: String, history: &[impl AsRef<str>]) {
+        self.query = query;
+        self.index = find_back(history, &self.query, history.len());
+    }
+
+    /// Looks for the next older entry containing the query, staying on the current match if there
+    /// are no more
+    pub fn next(&mut self, history: &[impl AsRef<str>]) {
+        let before = self.index.unwrap_or(history.len());
+        if let Some(index) = find_back(history, &self.query, before) {
+            self.index = Some(index);
+        }
+    }
+
+    pub fn matched<'a, T: AsRef<str>>(&self, history: &'a [T]) -> Option<&'a str> {
+        self.index.and_then(|i| history.get(i)).map(AsRef::as_ref)
+    }
+}
+
+/// Finds the newest entry before `before` which contains the query
+fn find_back(history: &[impl AsRef<str>], query: &str, before: usize) -> Option<usize> {
+    if query.is_empty() {
+        return None;
+    }
+    history[..before.min(history.len())]
+        .iter()
+        .rposition(|item| item.as_ref().contains(query))
+}
+
+#[cfg(test)]
+fn test_history() -> Vec<String> {
+    ["about", "conway --about", "diff a b", "conway"]
+        .map(String::from)
+        .to_vec()
+}
+
+#[test]
+fn test_cursor_keeps_draft() {
+    let history = test_history();
+    let mut cursor = HistoryCursor::default();
+    assert_eq!(cursor.older(&history, "dra").as_deref(), Some("conway"));
+    assert_eq!(
+        cursor.older(&history, "conway").as_deref(),
+        Some("diff a b")
+    );
+    assert_eq!(cursor.newer(&history).as_deref(), Some("conway"));
+    assert_eq!(cursor.newer(&history).as_deref(), Some("dra"));
+    // already showing the draft
+    assert_eq!(cursor.newer(&history), None);
+}
+
+#[test]
+fn test_cursor_bounds() {
+    let history = test_history();
+    let mut cursor = HistoryCursor::default();
+    for _ in 0..history.len() {
+        assert!(cursor.older(&history, "").is_some());
+    }
+    assert_eq!(cursor.older(&history, "about"), None);
+    assert_eq!(HistoryCursor::default().older(&[] as &[String], ""), None);
+}
+
+#[test]
+fn test_reverse_search() {
+    let history = test_history();
+    let mut search = ReverseSearch::new(String::new());
+    search.set_query("conway".into(), &history);
+    assert_eq!(search.matched(&history), Some("conway"));
+    search.next(&history);
+    assert_eq!(search.matched(&history), Some("conway --about"));
+    // no older matches, so the match stays put
+    search.next(&history);
+    assert_eq!(search.matched(&history), Some("conway --about"));
+    search.set_query("nothing".into(), &history);
+    assert_eq!(search.matched(&history), None);
+}
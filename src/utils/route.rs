@@ -0,0 +1,235 @@
+//! Conversions between URLs and lines of terminal input, so commands can be linked to directly.
+//!
+//! A URL's path segments become the words of a command and its query parameters become `--long`
+//! flags, so `/conway?preset=lwss&width=40` runs `conway --preset lwss --width 40`. The same form
+//! is accepted after a `#`, as in `/#/projects`. Lines that can't be written that way are kept
+//! whole in a `cmd` parameter instead, as in `/?cmd=about%20%26%26%20contact`. Paths which don't
+//! start with a command, like `/index.html`, don't run anything.
+
+use crate::utils::tokenize::{quote, split_chain, tokenize};
+
+const CMD_PARAM: &str = "cmd";
+
+/// Gets the line to run for a URL, given its path, query string and hash fragment. A path is only
+/// read as a line when `is_command` accepts its first segment.
+pub fn route_to_line(
+    path: &str,
+    query: &str,
+    hash: &str,
+    is_command: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let (path, query) = match hash.strip_prefix('#') {
+        Some(route) if route.starts_with('/') => route.split_once('?').unwrap_or((route, "")),
+        _ => (path, query),
+    };
+    let params: Vec<(String, String)> = query
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            (decode(key), decode(value))
+        })
+        .collect();
+
+    if let Some((_, line)) = params.iter().find(|(key, _)| key == CMD_PARAM) {
+        return Some(line.clone());
+    }
+
+    let mut words: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(decode)
+        .collect();
+    if !words.first().is_some_and(|word| is_command(word)) {
+        return None;
+    }
+    for (key, value) in params {
+        words.push(format!("--{key}"));
+        if !value.is_empty() {
+            words.push(value);
+        }
+    }
+
+    Some(
+        words
+            .iter()
+            .map(|word| quote(word))
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// Gets the URL (path and query) which runs a line
+pub fn line_to_route(line: &str) -> String {
+    path_route(line).unwrap_or_else(|| format!("/?{CMD_PARAM}={}", encode(line.trim())))
+}
+
+/// Writes a line as path segments and flags, if it can be read back the same way. Variables,
+/// chained commands, short flags and positional arguments after flags all need the `cmd` form.
+fn path_route(line: &str) -> Option<String> {
+    if line.contains('$') || split_chain(line).ok()?.len() != 1 {
+        return None;
+    }
+    let tokens = tokenize(line, |_| None).ok()?;
+
+    let split = tokens
+        .iter()
+        .position(|token| token.starts_with('-'))
+        .unwrap_or(tokens.len());
+    let (words, flags) = tokens.split_at(split);
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut params = Vec::new();
+    let mut flags = flags.iter().peekable();
+    while let Some(flag) = flags.next() {
+        let key = flag.strip_prefix("--").filter(|key| !key.is_empty())?;
+        match flags.next_if(|value| !value.starts_with('-')) {
+            Some(value) => params.push(format!("{}={}", encode(key), encode(value))),
+            None => params.push(encode(key)),
+        }
+    }
+
+    let path = words
+        .iter()
+        .map(|word| encode(word))
+        .collect::<Vec<_>>()
+        .join("/");
+    let query = if params.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", params.join("&"))
+    };
+
+    // empty values and the like don't survive the trip, so make sure the line comes back intact
+    let line_back = route_to_line(&path, &query, "", |_| true)?;
+    (tokenize(&line_back, |_| None).ok()? == tokens).then(|| format!("/{path}{query}"))
+}
+
+/// Percent-encodes everything besides unreserved characters
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Decodes percent-encoded text, also treating `+` as a space like in form data
+fn decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = rest
+                    .get(..2)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match hex {
+                    Some(decoded) => {
+                        bytes.push(decoded);
+                        rest = &rest[2..];
+                    }
+                    None => bytes.push(b'%'),
+                }
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+fn is_command(word: &str) -> bool {
+    crate::commands::REGISTRY.get(word).is_some()
+}
+
+#[test]
+fn test_route_to_line() {
+    assert_eq!(
+        route_to_line("/conway", "?preset=lwss&width=40", "", is_command).as_deref(),
+        Some("conway --preset lwss --width 40")
+    );
+    assert_eq!(
+        route_to_line("/", "", "#/projects", is_command).as_deref(),
+        Some("projects")
+    );
+    assert_eq!(
+        route_to_line("/history/index/2", "", "", is_command).as_deref(),
+        Some("history index 2")
+    );
+    assert_eq!(
+        route_to_line("/conway", "?about", "", is_command).as_deref(),
+        Some("conway --about")
+    );
+    assert_eq!(
+        route_to_line("/diff/Hello%20World/hello+world", "", "", is_command).as_deref(),
+        Some("diff 'Hello World' 'hello world'")
+    );
+    assert_eq!(
+        route_to_line("/", "?cmd=about%20%26%26%20contact", "", is_command).as_deref(),
+        Some("about && contact")
+    );
+    assert_eq!(route_to_line("/", "", "", is_command), None);
+    assert_eq!(
+        route_to_line("/", "?utm_source=x", "#section", is_command),
+        None
+    );
+    // static files and unknown paths aren't commands
+    assert_eq!(route_to_line("/index.html", "", "", is_command), None);
+    assert_eq!(route_to_line("/img/taxonia1.png", "", "", is_command), None);
+    assert_eq!(
+        route_to_line("/ll", "", "", |word| word == "ll").as_deref(),
+        Some("ll")
+    );
+}
+
+#[test]
+fn test_line_to_route() {
+    assert_eq!(
+        line_to_route("conway --preset lwss --width 40"),
+        "/conway?preset=lwss&width=40"
+    );
+    assert_eq!(line_to_route("projects"), "/projects");
+    assert_eq!(line_to_route("conway --about"), "/conway?about");
+    assert_eq!(
+        line_to_route(r#"diff "Hello World" b"#),
+        "/diff/Hello%20World/b"
+    );
+    assert_eq!(
+        line_to_route("about && contact"),
+        "/?cmd=about%20%26%26%20contact"
+    );
+    assert_eq!(line_to_route("conway -a"), "/?cmd=conway%20-a");
+    assert_eq!(line_to_route("diff '' b"), "/?cmd=diff%20%27%27%20b");
+    assert_eq!(line_to_route("set A=$B"), "/?cmd=set%20A%3D%24B");
+}
+
+#[test]
+fn test_route_round_trip() {
+    for line in [
+        "conway --preset lwss --width 40",
+        "diff 'a/b' 'c d'",
+        "history index 2 && about",
+        "diff 'it'\\''s' \"100%\"",
+    ] {
+        let route = line_to_route(line);
+        let (path, query) = route.split_once('?').unwrap_or((&route, ""));
+        let line_back = route_to_line(path, query, "", |_| true).unwrap();
+        assert_eq!(
+            tokenize(&line_back, |_| None),
+            tokenize(line, |_| None),
+            "{route}"
+        );
+    }
+}
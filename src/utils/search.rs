@@ -0,0 +1,423 @@
+//! Full-text search over the portfolio's content. The index is built the first time it's
+//! searched, with each project, category of skills, the about text and the contact links as a
+//! document.
+//!
+//! A query is made of clauses which must all be found in a document. A clause with several words
+//! is a phrase, which matches those words in order on the same line, and a word ending in `*`
+//! matches any word starting with it. Words are split at punctuation, so `prc-rs` is a phrase too.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use crate::commands::contact::CONTACTS;
+use crate::components::about::{BIO, HOBBIES};
+use crate::content::{PROJECT_GROUPS, SKILLS, slug};
+use crate::utils::vfs::skill_path;
+
+/// How much more a match in a document's title counts than one in its text
+const TITLE_WEIGHT: u32 = 3;
+/// The longest a snippet can be, in characters, before it's cut down around the first match
+const SNIPPET_LENGTH: usize = 100;
+
+pub static INDEX: LazyLock<SearchIndex> = LazyLock::new(|| SearchIndex::new(documents()));
+
+/// Something that can be found by searching
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    pub title: String,
+    /// The line which shows the document in full
+    pub command: String,
+    /// The document's text, where the first line is the title
+    pub lines: Vec<String>,
+}
+
+impl Document {
+    fn new(title: &str, command: String, text: impl IntoIterator<Item = String>) -> Self {
+        Document {
+            title: title.to_string(),
+            command,
+            lines: std::iter::once(title.to_string()).chain(text).collect(),
+        }
+    }
+}
+
+/// Text with the parts that matched a query marked, as byte ranges
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlighted {
+    pub text: String,
+    pub matches: Vec<Range<usize>>,
+}
+
+/// A document found by a search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hit<'a> {
+    pub document: &'a Document,
+    pub score: u32,
+    pub title: Highlighted,
+    /// The line with the most matches, or the first line of text if only the title matched
+    pub snippet: Highlighted,
+}
+
+/// A word of a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct QueryWord {
+    text: String,
+    prefix: bool,
+}
+
+/// Where a word appears: the document, the line in it, and the word's index in the line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Posting {
+    doc: usize,
+    line: usize,
+    word: usize,
+}
+
+pub struct SearchIndex {
+    documents: Vec<Document>,
+    /// The words of each line of each document, as byte ranges into the line
+    words: Vec<Vec<Vec<Range<usize>>>>,
+    /// Where each word appears, kept sorted so prefixes can be looked up as a range
+    postings: BTreeMap<String, Vec<Posting>>,
+}
+
+impl SearchIndex {
+    pub fn new(documents: Vec<Document>) -> Self {
+        let mut postings: BTreeMap<String, Vec<Posting>> = BTreeMap::new();
+        let words: Vec<Vec<Vec<Range<usize>>>> = documents
+            .iter()
+            .map(|document| {
+                document
+                    .lines
+                    .iter()
+                    .map(|line| split_words(line))
+                    .collect()
+            })
+            .collect();
+        for (doc, lines) in words.iter().enumerate() {
+            for (line, ranges) in lines.iter().enumerate() {
+                for (word, range) in ranges.iter().enumerate() {
+                    let term = documents[doc].lines[line][range.clone()].to_lowercase();
+                    postings
+                        .entry(term)
+                        .or_default()
+                        .push(Posting { doc, line, word });
+                }
+            }
+        }
+        SearchIndex {
+            documents,
+            words,
+            postings,
+        }
+    }
+
+    /// Finds the documents matching every clause of a query, best first
+    pub fn search(&self, clauses: &[String]) -> Vec<Hit<'_>> {
+        let clauses: Vec<Vec<QueryWord>> = clauses
+            .iter()
+            .map(|clause| parse_clause(clause))
+            .filter(|words| !words.is_empty())
+            .collect();
+        if clauses.is_empty() {
+            return Vec::new();
+        }
+
+        // only documents which match every clause are kept
+        let mut found = self.find(&clauses[0]);
+        for clause in &clauses[1..] {
+            let mut matched = self.find(clause);
+            found.retain(|doc, _| matched.contains_key(doc));
+            for (doc, matches) in &mut found {
+                matches.append(matched.get_mut(doc).expect("kept documents matched"));
+            }
+        }
+
+        let mut hits: Vec<Hit> = found
+            .into_iter()
+            .map(|(doc, matches)| self.hit(doc, &matches))
+            .collect();
+        // the sort is stable, so equally good hits keep the order of the content
+        hits.sort_by_key(|hit| std::cmp::Reverse(hit.score));
+        hits
+    }
+
+    /// Finds where a clause matches in each document, as (line, first word, last word)
+    fn find(&self, clause: &[QueryWord]) -> BTreeMap<usize, Vec<(usize, usize, usize)>> {
+        let mut found: BTreeMap<usize, Vec<(usize, usize, usize)>> = BTreeMap::new();
+        for posting in self.lookup(&clause[0]) {
+            if let Some(end) = self.phrase_end(posting, clause) {
+                found
+                    .entry(posting.doc)
+                    .or_default()
+                    .push((posting.line, posting.word, end));
+            }
+        }
+        found
+    }
+
+    /// Finds where a word appears, including every word it's a prefix of
+    fn lookup(&self, word: &QueryWord) -> Vec<Posting> {
+        if !word.prefix {
+            return self.postings.get(&word.text).cloned().unwrap_or_default();
+        }
+        self.postings
+            .range(word.text.clone()..)
+            .take_while(|(term, _)| term.starts_with(&word.text))
+            .flat_map(|(_, postings)| postings.iter().copied())
+            .collect()
+    }
+
+    /// Checks that the rest of a phrase follows its first word, returning the index of its last
+    /// word if it does
+    fn phrase_end(&self, start: Posting, phrase: &[QueryWord]) -> Option<usize> {
+        let line = &self.documents[start.doc].lines[start.line];
+        let ranges = &self.words[start.doc][start.line];
+        for (offset, word) in phrase.iter().enumerate().skip(1) {
+            let term = line[ranges.get(start.word + offset)?.clone()].to_lowercase();
+            let matched = if word.prefix {
+                term.starts_with(&word.text)
+            } else {
+                term == word.text
+            };
+            if !matched {
+                return None;
+            }
+        }
+        Some(start.word + phrase.len() - 1)
+    }
+
+    fn hit(&self, doc: usize, matches: &[(usize, usize, usize)]) -> Hit<'_> {
+        let document = &self.documents[doc];
+        let ranges = &self.words[doc];
+        let score = matches
+            .iter()
+            .map(|(line, _, _)| if *line == 0 { TITLE_WEIGHT } else { 1 })
+            .sum();
+
+        let highlights = |line: usize| -> Vec<Range<usize>> {
+            let mut highlights: Vec<Range<usize>> = matches
+                .iter()
+                .filter(|(l, _, _)| *l == line)
+                .map(|(_, first, last)| ranges[line][*first].start..ranges[line][*last].end)
+                .collect();
+            highlights.sort_by_key(|range| range.start);
+            // a word can match more than one clause, so overlapping ranges are merged
+            let mut merged: Vec<Range<usize>> = Vec::new();
+            for range in highlights {
+                match merged.last_mut() {
+                    Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                    _ => merged.push(range),
+                }
+            }
+            merged
+        };
+
+        // the line of text with the most matches, or else the first one
+        let snippet_line = (1..document.lines.len()).max_by_key(|line| {
+            let count = matches.iter().filter(|(l, _, _)| l == line).count();
+            (count, std::cmp::Reverse(*line))
+        });
+        let snippet = match snippet_line {
+            Some(line) => snippet(&document.lines[line], highlights(line)),
+            None => Highlighted {
+                text: String::new(),
+                matches: Vec::new(),
+            },
+        };
+
+        Hit {
+            document,
+            score,
+            title: Highlighted {
+                text: document.title.clone(),
+                matches: highlights(0),
+            },
+            snippet,
+        }
+    }
+}
+
+/// Splits a line into words of letters and numbers, as byte ranges
+fn split_words(line: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (start, c.is_alphanumeric()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                words.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+fn parse_clause(clause: &str) -> Vec<QueryWord> {
+    clause
+        .split_whitespace()
+        .flat_map(|word| {
+            let (word, prefix) = match word.strip_suffix('*') {
+                Some(word) => (word, true),
+                None => (word, false),
+            };
+            // a word is split into letters and numbers like the words it's matched to, so
+            // `prc-rs` is a phrase of `prc` and `rs`, where only the last part can be a prefix
+            let pieces = split_words(word);
+            let last = pieces.len().saturating_sub(1);
+            pieces
+                .into_iter()
+                .enumerate()
+                .map(move |(i, range)| QueryWord {
+                    text: word[range].to_lowercase(),
+                    prefix: prefix && i == last,
+                })
+        })
+        .collect()
+}
+
+/// Cuts a long line down to the part around its first match
+fn snippet(line: &str, matches: Vec<Range<usize>>) -> Highlighted {
+    if line.chars().count() <= SNIPPET_LENGTH {
+        return Highlighted {
+            text: line.to_string(),
+            matches,
+        };
+    }
+    let first = matches.first().map_or(0, |range| range.start);
+    // start a little before the first match, on a character boundary
+    let start = line[..first]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_LENGTH / 4)
+        .map_or(0, |(i, _)| i);
+    let end = line[start..]
+        .char_indices()
+        .nth(SNIPPET_LENGTH)
+        .map_or(line.len(), |(i, _)| start + i);
+
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if end < line.len() { "…" } else { "" };
+    let shift = |i: usize| i - start + prefix.len();
+    Highlighted {
+        text: format!("{prefix}{}{suffix}", &line[start..end]),
+        matches: matches
+            .into_iter()
+            .filter(|range| range.start >= start && range.end <= end)
+            .map(|range| shift(range.start)..shift(range.end))
+            .collect(),
+    }
+}
+
+/// Gathers everything that can be searched
+fn documents() -> Vec<Document> {
+    let mut documents = Vec::new();
+    for group in PROJECT_GROUPS.iter() {
+        for project in &group.projects {
+            let mut text = vec![project.summary.clone()];
+            text.extend(project.bullets.iter().cloned());
+            text.extend(
+                project
+                    .deployment
+                    .as_ref()
+                    .map(|url| format!("Deployment: {url}")),
+            );
+            text.extend(
+                project
+                    .sources
+                    .iter()
+                    .map(|(label, url)| format!("{label}: {url}")),
+            );
+            text.push(format!("Tags: {}", project.tags.join(", ")));
+            let command = format!("project {}", slug(&project.title));
+            documents.push(Document::new(&project.title, command, text));
+        }
+    }
+    for skill in SKILLS.iter() {
+        let command = format!("cat {}", skill_path(&skill.category));
+        let text = skill.bullets.iter().cloned();
+        documents.push(Document::new(&skill.category, command, text));
+    }
+    let about = BIO
+        .iter()
+        .map(|paragraph| paragraph.replace("**", ""))
+        .chain(HOBBIES.iter().map(ToString::to_string));
+    documents.push(Document::new("About", "about".to_string(), about));
+    let contact = CONTACTS
+        .iter()
+        .map(|contact| format!("{} {}", contact.heading, contact.label));
+    documents.push(Document::new("Contact", "contact".to_string(), contact));
+    documents
+}
+
+#[cfg(test)]
+fn titles(query: &[&str]) -> Vec<String> {
+    let query: Vec<String> = query.iter().map(ToString::to_string).collect();
+    INDEX
+        .search(&query)
+        .into_iter()
+        .map(|hit| hit.title.text)
+        .collect()
+}
+
+#[test]
+fn test_search() {
+    // a match in the title ranks first
+    assert_eq!(titles(&["prc"])[0], "prc-rs");
+    // every clause has to match
+    assert_eq!(titles(&["param", "python"]), ["pyprc"]);
+    assert!(titles(&["param", "zzz"]).is_empty());
+    // phrases match words in order, and prefixes match the start of words
+    assert_eq!(titles(&["param files"]), ["prc-rs", "pyprc"]);
+    assert!(titles(&["files param"]).is_empty());
+    assert!(titles(&["paraco*"]).contains(&"paracobNET".to_string()));
+    assert!(titles(&["paraco"]).is_empty());
+    assert_eq!(titles(&["labradoodle"]), ["About"]);
+    assert!(titles(&["*"]).is_empty());
+    // punctuation in a word matches the same words apart, so projects can be found by title
+    assert_eq!(titles(&["prc-rs"])[0], "prc-rs");
+    assert_eq!(titles(&["PRC-r*"])[0], "prc-rs");
+}
+
+#[test]
+fn test_search_highlights() {
+    let query = ["edit param".to_string()];
+    let hits = INDEX.search(&query);
+    let pyprc = &hits[0].snippet;
+    assert_eq!(hits[0].document.command, "project pyprc");
+    assert_eq!(&pyprc.text[pyprc.matches[0].clone()], "edit param");
+
+    let query = ["lady".to_string()];
+    let about = &INDEX.search(&query)[0].snippet;
+    assert_eq!(&about.text[about.matches[0].clone()], "Lady");
+
+    let line = "a ".repeat(100) + "needle";
+    let needle = 200..206;
+    let cut = snippet(&line, vec![needle]);
+    assert!(cut.text.starts_with('…') && cut.text.ends_with("needle"));
+    assert_eq!(&cut.text[cut.matches[0].clone()], "needle");
+}
+
+#[test]
+fn test_overlapping_highlights() {
+    // a word and a phrase over the same words are shown as one highlight
+    let query = ["param".to_string(), "param files".to_string()];
+    for hit in INDEX.search(&query) {
+        for highlighted in [&hit.title, &hit.snippet] {
+            for pair in highlighted.matches.windows(2) {
+                assert!(pair[0].end < pair[1].start, "{:?}", highlighted.matches);
+            }
+        }
+    }
+    let hits = INDEX.search(&query);
+    let snippet = &hits[0].snippet;
+    assert!(
+        snippet
+            .matches
+            .iter()
+            .any(|range| &snippet.text[range.clone()] == "param files")
+    );
+}
@@ -0,0 +1,80 @@
+//! The startup script, `.portfoliorc`, which runs when the terminal opens. It's kept in
+//! LocalStorage so visitors can set up aliases and the like that stick around between visits.
+
+#[cfg(test)]
+use crate::utils::storage::MemoryStore;
+use crate::utils::storage::{KeyValueStore, LocalStore};
+use crate::utils::tokenize::strip_comment;
+
+const STARTUP_KEY: &str = "portfolio.rc";
+
+/// The name the startup script goes by, as in `source .portfoliorc`
+pub const STARTUP_FILE: &str = ".portfoliorc";
+
+/// The startup script, saved in a [`KeyValueStore`], which is LocalStorage by default
+#[derive(Debug, Clone, Default)]
+pub struct StartupScript<S = LocalStore> {
+    store: S,
+    script: String,
+}
+
+impl StartupScript {
+    pub fn new() -> Self {
+        StartupScript::load(LocalStore)
+    }
+}
+
+impl<S: KeyValueStore> StartupScript<S> {
+    /// Reads the script saved in a store, which it's saved back to when it changes
+    pub fn load(store: S) -> Self {
+        let script = store.get_json(STARTUP_KEY).unwrap_or_default();
+        StartupScript { store, script }
+    }
+
+    pub fn script(&self) -> &str {
+        &self.script
+    }
+
+    /// Saves a new script, where a blank one removes it
+    pub fn save(&mut self, script: &str) {
+        if script.trim().is_empty() {
+            self.store.delete(STARTUP_KEY);
+            self.script = String::new();
+        } else {
+            self.store.set_json(STARTUP_KEY, &script);
+            self.script = script.to_string();
+        }
+    }
+}
+
+/// Gets the lines of a script which should run, numbered from 1. Comments starting with `#` are
+/// removed, and lines left blank are skipped.
+pub fn script_lines(script: &str) -> impl Iterator<Item = (usize, &str)> {
+    script
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, strip_comment(line).trim()))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+#[test]
+fn test_script_lines() {
+    let script = "# set things up\nalias p=projects\n\n  set NAME=world  \r\nabout # trailing\n";
+    assert_eq!(
+        script_lines(script).collect::<Vec<_>>(),
+        [(2, "alias p=projects"), (4, "set NAME=world"), (5, "about")]
+    );
+}
+
+#[test]
+fn test_saved_script() {
+    let mut startup = StartupScript::load(MemoryStore::default());
+    assert_eq!(startup.script(), "");
+    startup.save("alias p=projects\n");
+    let mut saved = StartupScript::load(startup.store.clone());
+    assert_eq!(saved.script(), "alias p=projects\n");
+    // saving a blank script removes it
+    saved.save("  \n");
+    assert_eq!(saved.script(), "");
+    assert_eq!(saved.store.get(STARTUP_KEY), None);
+}
@@ -0,0 +1,263 @@
+//! Data kept between visits, in LocalStorage in the browser. Stores save through the
+//! [`KeyValueStore`] trait, so they can also keep their data in memory or in a file. Versioned
+//! data is saved along with the version of its shape, so data saved in an older shape can be
+//! migrated when it's read instead of being lost.
+
+use std::collections::BTreeMap;
+
+use gloo::storage::{LocalStorage, SessionStorage, Storage};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Somewhere to keep strings under keys, like the browser's LocalStorage
+pub trait KeyValueStore {
+    fn get(&self, key: &str) -> Option<String>;
+
+    fn set(&mut self, key: &str, value: &str);
+
+    fn delete(&mut self, key: &str);
+
+    /// Reads a value saved as JSON, which is `None` when it's missing or can't be read
+    fn get_json<T: DeserializeOwned>(&self, key: &str) -> Option<T>
+    where
+        Self: Sized,
+    {
+        serde_json::from_str(&self.get(key)?).ok()
+    }
+
+    fn set_json<T: Serialize>(&mut self, key: &str, value: &T)
+    where
+        Self: Sized,
+    {
+        if let Ok(json) = serde_json::to_string(value) {
+            self.set(key, &json);
+        }
+    }
+}
+
+/// The browser's LocalStorage, which lasts between visits
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalStore;
+
+impl KeyValueStore for LocalStore {
+    fn get(&self, key: &str) -> Option<String> {
+        LocalStorage::raw().get_item(key).ok().flatten()
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        let _ = LocalStorage::raw().set_item(key, value);
+    }
+
+    fn delete(&mut self, key: &str) {
+        LocalStorage::delete(key);
+    }
+}
+
+/// The browser's SessionStorage, which lasts until the tab is closed. Nothing is kept only for a
+/// tab yet, and it can't be used outside the browser, so it isn't made anywhere.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionStore;
+
+impl KeyValueStore for SessionStore {
+    fn get(&self, key: &str) -> Option<String> {
+        SessionStorage::raw().get_item(key).ok().flatten()
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        let _ = SessionStorage::raw().set_item(key, value);
+    }
+
+    fn delete(&mut self, key: &str) {
+        SessionStorage::delete(key);
+    }
+}
+
+/// Values kept in memory, which are lost when the store is dropped
+#[cfg_attr(not(test), allow(dead_code))]
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    values: BTreeMap<String, String>,
+}
+
+impl KeyValueStore for MemoryStore {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.get(key).cloned()
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    fn delete(&mut self, key: &str) {
+        self.values.remove(key);
+    }
+}
+
+/// Values kept in a JSON file, for running outside the browser. The file is read on every call,
+/// so other stores writing to it are seen.
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg_attr(not(test), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    path: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg_attr(not(test), allow(dead_code))]
+impl FileStore {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        FileStore { path: path.into() }
+    }
+
+    /// Reads every value in the file, where a missing or unreadable file has none
+    fn values(&self) -> BTreeMap<String, String> {
+        std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    fn write(&self, values: &BTreeMap<String, String>) {
+        if let Ok(json) = serde_json::to_string_pretty(values) {
+            let _ = std::fs::write(&self.path, json);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl KeyValueStore for FileStore {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values().remove(key)
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        let mut values = self.values();
+        values.insert(key.to_string(), value.to_string());
+        self.write(&values);
+    }
+
+    fn delete(&mut self, key: &str) {
+        let mut values = self.values();
+        if values.remove(key).is_some() {
+            self.write(&values);
+        }
+    }
+}
+
+/// How a kind of data is stored
+pub struct Schema<T> {
+    pub key: &'static str,
+    /// The version data is saved with, which goes up whenever its shape changes
+    pub version: u32,
+    /// Keys the data was kept under before, which are read when there's nothing at `key`
+    pub legacy_keys: &'static [&'static str],
+    /// Converts data saved with an older version, where version 0 is data saved without a
+    /// version
+    pub migrate: fn(u32, Value) -> Result<T, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope<T> {
+    version: u32,
+    data: T,
+}
+
+/// Stored data which couldn't be read, which is kept so it can be backed up before it's replaced
+#[derive(Debug, Clone, PartialEq)]
+pub struct Corrupt {
+    /// The key the data is stored under
+    pub key: &'static str,
+    pub raw: String,
+    /// Why the data couldn't be read
+    pub reason: String,
+}
+
+impl<T: Serialize + DeserializeOwned> Schema<T> {
+    /// Reads the stored data, moving it over from an older key or version if it has to. Returns
+    /// `None` when nothing is stored.
+    pub fn load(&self, store: &mut impl KeyValueStore) -> Result<Option<T>, Corrupt> {
+        for key in std::iter::once(&self.key).chain(self.legacy_keys) {
+            let Some(raw) = store.get(key) else {
+                continue;
+            };
+            let data = self
+                .decode(&raw)
+                .map_err(|reason| Corrupt { key, raw, reason })?;
+            if *key != self.key {
+                self.save(store, &data);
+                store.delete(key);
+            }
+            return Ok(Some(data));
+        }
+        Ok(None)
+    }
+
+    pub fn save(&self, store: &mut impl KeyValueStore, data: &T) {
+        let envelope = Envelope {
+            version: self.version,
+            data,
+        };
+        store.set_json(self.key, &envelope);
+    }
+
+    /// Reads data saved with this version or any older one
+    pub fn decode(&self, raw: &str) -> Result<T, String> {
+        let value: Value =
+            serde_json::from_str(raw).map_err(|err| format!("it isn't valid JSON ({err})"))?;
+        match serde_json::from_value::<Envelope<Value>>(value.clone()) {
+            Ok(Envelope { version, data }) if version == self.version => {
+                serde_json::from_value(data).map_err(mismatch)
+            }
+            Ok(Envelope { version, .. }) if version > self.version => Err(format!(
+                "it was saved by a newer version of the site (version {version})"
+            )),
+            Ok(Envelope { version, data }) => (self.migrate)(version, data),
+            Err(_) => (self.migrate)(0, value),
+        }
+    }
+}
+
+/// Describes data that's valid JSON, but not in the shape that was expected
+pub fn mismatch(err: serde_json::Error) -> String {
+    format!("it isn't in the expected shape ({err})")
+}
+
+/// Copies data which couldn't be read to a key of its own, then removes it so it can be
+/// replaced. Earlier backups are kept, with later ones numbered like `portfolio.history.backup.2`.
+/// Returns the key it was copied to.
+pub fn back_up(store: &mut impl KeyValueStore, corrupt: &Corrupt) -> String {
+    let backup_key = std::iter::once(format!("{}.backup", corrupt.key))
+        .chain((2..).map(|n| format!("{}.backup.{n}", corrupt.key)))
+        .find(|key| store.get(key).is_none())
+        .expect("there are endless keys to try");
+    store.set(&backup_key, &corrupt.raw);
+    store.delete(corrupt.key);
+    backup_key
+}
+
+/// Removes data which couldn't be read, without keeping a copy
+pub fn reset(store: &mut impl KeyValueStore, corrupt: &Corrupt) {
+    store.delete(corrupt.key);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_file_store() {
+    let path = std::env::temp_dir().join(format!("portfolio-test-{}.json", std::process::id()));
+    let mut store = FileStore::new(&path);
+    store.set("portfolio.rc", "alias ll=ls");
+    store.set_json("portfolio.aliases", &["ll"]);
+    // another store on the same file sees the same values
+    let mut other = FileStore::new(&path);
+    assert_eq!(other.get("portfolio.rc").as_deref(), Some("alias ll=ls"));
+    assert_eq!(
+        other.get_json::<Vec<String>>("portfolio.aliases"),
+        Some(vec!["ll".to_string()])
+    );
+    other.delete("portfolio.rc");
+    assert_eq!(store.get("portfolio.rc"), None);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(store.get("portfolio.aliases"), None);
+}
@@ -0,0 +1,426 @@
+use std::borrow::Cow;
+use std::fmt::{self, Display};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// An error encountered while splitting a line of input into arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenizeError {
+    /// A quote was opened but never closed
+    UnterminatedQuote(char),
+    /// The line ended with a backslash that has nothing left to escape
+    TrailingEscape,
+    /// A `${` was opened but never closed
+    UnterminatedVariable,
+    /// A chaining operator appeared without a command on one of its sides
+    MissingCommand(Connector),
+}
+
+impl Display for TokenizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenizeError::UnterminatedQuote(q) => {
+                write!(f, "unterminated quote: missing closing {q}")
+            }
+            TokenizeError::TrailingEscape => write!(f, "unexpected end of input after '\\'"),
+            TokenizeError::UnterminatedVariable => {
+                write!(f, "unterminated variable: missing closing }}")
+            }
+            TokenizeError::MissingCommand(op) => {
+                write!(f, "expected a command next to '{}'", op.as_str())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TokenizeError {}
+
+/// The operator joining a command to the one before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connector {
+    /// `;`, which runs the command regardless of the previous result
+    Sequence,
+    /// `&&`, which runs the command only if the previous one succeeded
+    And,
+    /// `||`, which runs the command only if the previous one failed
+    Or,
+}
+
+impl Connector {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Connector::Sequence => ";",
+            Connector::And => "&&",
+            Connector::Or => "||",
+        }
+    }
+
+    /// Whether a command joined by this operator should run, given the status of the last command
+    /// that ran
+    pub fn should_run(&self, last_ok: bool) -> bool {
+        match self {
+            Connector::Sequence => true,
+            Connector::And => last_ok,
+            Connector::Or => !last_ok,
+        }
+    }
+}
+
+/// Splits a line into the commands joined by `;`, `&&` and `||`, ignoring any operators that are
+/// quoted or escaped. The first command is always paired with [`Connector::Sequence`]. Each
+/// command is returned as its raw text, which still needs to be passed through [`tokenize`].
+pub fn split_chain(input: &str) -> Result<Vec<(Connector, &str)>, TokenizeError> {
+    let mut commands = Vec::new();
+    let mut connector = Connector::Sequence;
+    let mut start = 0;
+    let mut quote: Option<char> = None;
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let op = match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => {
+                quote = None;
+                continue;
+            }
+            (Some('"'), '\\') | (None, '\\') => {
+                chars.next();
+                continue;
+            }
+            (Some(_), _) => continue,
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                continue;
+            }
+            (None, ';') => Connector::Sequence,
+            (None, '&') if chars.next_if(|(_, c)| *c == '&').is_some() => Connector::And,
+            (None, '|') if chars.next_if(|(_, c)| *c == '|').is_some() => Connector::Or,
+            _ => continue,
+        };
+
+        let command = &input[start..i];
+        if command.trim().is_empty() {
+            return Err(TokenizeError::MissingCommand(op));
+        }
+        commands.push((connector, command));
+        connector = op;
+        start = i + op.as_str().len();
+    }
+    if let Some(q) = quote {
+        return Err(TokenizeError::UnterminatedQuote(q));
+    }
+
+    let command = &input[start..];
+    if !command.trim().is_empty() {
+        commands.push((connector, command));
+    } else if connector != Connector::Sequence {
+        // a trailing `;` is harmless, but `&&` and `||` need something to run afterwards
+        return Err(TokenizeError::MissingCommand(connector));
+    }
+
+    Ok(commands)
+}
+
+/// Cuts a comment off the end of a line. Like in a shell, a comment starts at a `#` which begins
+/// a word and isn't quoted or escaped, so `a#b`, `'#'` and `\#` are kept.
+pub fn strip_comment(input: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut word_start = true;
+    let mut chars = input.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                chars.next();
+            }
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '#') if word_start => return &input[..i],
+            _ => {}
+        }
+        word_start = quote.is_none() && (c.is_whitespace() || ";&|".contains(c));
+    }
+    input
+}
+
+/// Splits a line of input into arguments, following the quoting rules of a POSIX shell:
+///
+/// - unquoted whitespace separates arguments
+/// - single quotes preserve everything between them literally
+/// - double quotes preserve everything except `\"`, `\\` and `\$` escapes, and variables
+/// - outside of quotes, a backslash escapes the character after it
+///
+/// Quoted sections can be joined with other text to form a single argument (`a"b c"` is `ab c`),
+/// and an empty pair of quotes produces an empty argument.
+///
+/// Variables written as `$NAME`, `${NAME}` or `$?` are replaced using `lookup`, where unknown
+/// variables become empty. Like in a shell, the value of an unquoted variable is split into
+/// separate arguments on whitespace, while a quoted one stays as a single argument.
+pub fn tokenize(
+    input: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<Vec<String>, TokenizeError> {
+    let mut tokens = Vec::new();
+    // the argument being built, which is `None` until something (even empty quotes) starts it
+    let mut current: Option<String> = None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                let token = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => token.push(c),
+                        None => return Err(TokenizeError::UnterminatedQuote('\'')),
+                    }
+                }
+            }
+            '"' => {
+                let token = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$')) => token.push(c),
+                            Some(c) => {
+                                token.push('\\');
+                                token.push(c);
+                            }
+                            None => return Err(TokenizeError::UnterminatedQuote('"')),
+                        },
+                        Some('$') => match read_variable(&mut chars)? {
+                            Some(name) => token.push_str(&lookup(&name).unwrap_or_default()),
+                            None => token.push('$'),
+                        },
+                        Some(c) => token.push(c),
+                        None => return Err(TokenizeError::UnterminatedQuote('"')),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => current.get_or_insert_with(String::new).push(c),
+                None => return Err(TokenizeError::TrailingEscape),
+            },
+            '$' => match read_variable(&mut chars)? {
+                Some(name) => {
+                    for c in lookup(&name).unwrap_or_default().chars() {
+                        if c.is_whitespace() {
+                            tokens.extend(current.take());
+                        } else {
+                            current.get_or_insert_with(String::new).push(c);
+                        }
+                    }
+                }
+                None => current.get_or_insert_with(String::new).push('$'),
+            },
+            c if c.is_whitespace() => tokens.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    tokens.extend(current);
+
+    Ok(tokens)
+}
+
+/// Reads the name of a variable following a `$`, or `None` if the `$` doesn't start one
+fn read_variable(chars: &mut Peekable<Chars>) -> Result<Option<String>, TokenizeError> {
+    match chars.peek() {
+        Some('?') => {
+            chars.next();
+            Ok(Some("?".to_string()))
+        }
+        Some('{') => {
+            chars.next();
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some('}') => return Ok(Some(name)),
+                    Some(c) => name.push(c),
+                    None => return Err(TokenizeError::UnterminatedVariable),
+                }
+            }
+        }
+        Some(c) if is_variable_start(*c) => {
+            let mut name = String::new();
+            while let Some(c) = chars.next_if(|c| is_variable_char(*c)) {
+                name.push(c);
+            }
+            Ok(Some(name))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub fn is_variable_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+pub fn is_variable_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Quotes an argument so that [`tokenize`] reads it back unchanged
+pub fn quote(arg: &str) -> Cow<'_, str> {
+    let is_plain = |c: char| c.is_alphanumeric() || "-_./:=,@+%^".contains(c);
+    if !arg.is_empty() && arg.chars().all(is_plain) {
+        Cow::Borrowed(arg)
+    } else {
+        Cow::Owned(format!("'{}'", arg.replace('\'', r"'\''")))
+    }
+}
+
+#[cfg(test)]
+fn no_vars(_: &str) -> Option<String> {
+    None
+}
+
+#[test]
+fn test_tokenize_whitespace() {
+    assert_eq!(
+        tokenize("  history   index 3 ", no_vars).unwrap(),
+        ["history", "index", "3"]
+    );
+    assert!(tokenize("   ", no_vars).unwrap().is_empty());
+}
+
+#[test]
+fn test_tokenize_quotes() {
+    assert_eq!(
+        tokenize(r#"diff "Hello World" 'hello world'"#, no_vars).unwrap(),
+        ["diff", "Hello World", "hello world"]
+    );
+    assert_eq!(
+        tokenize(r#"a"b c"d '' """#, no_vars).unwrap(),
+        ["ab cd", "", ""]
+    );
+    assert_eq!(
+        tokenize(r#"'say "hi"' "it's""#, no_vars).unwrap(),
+        [r#"say "hi""#, "it's"]
+    );
+}
+
+#[test]
+fn test_tokenize_escapes() {
+    assert_eq!(tokenize(r"hello\ world", no_vars).unwrap(), ["hello world"]);
+    assert_eq!(
+        tokenize(r#""a \"quote\" and \\ \n""#, no_vars).unwrap(),
+        [r#"a "quote" and \ \n"#]
+    );
+    assert_eq!(
+        tokenize(r"'no \escapes'", no_vars).unwrap(),
+        [r"no \escapes"]
+    );
+}
+
+#[test]
+fn test_tokenize_errors() {
+    assert_eq!(
+        tokenize("'open", no_vars),
+        Err(TokenizeError::UnterminatedQuote('\''))
+    );
+    assert_eq!(
+        tokenize(r#"diff "open"#, no_vars),
+        Err(TokenizeError::UnterminatedQuote('"'))
+    );
+    assert_eq!(
+        tokenize(r"trailing\", no_vars),
+        Err(TokenizeError::TrailingEscape)
+    );
+}
+
+#[test]
+fn test_split_chain() {
+    assert_eq!(
+        split_chain("history clear && about").unwrap(),
+        [
+            (Connector::Sequence, "history clear "),
+            (Connector::And, " about")
+        ]
+    );
+    assert_eq!(
+        split_chain("a;b||c;").unwrap(),
+        [
+            (Connector::Sequence, "a"),
+            (Connector::Sequence, "b"),
+            (Connector::Or, "c")
+        ]
+    );
+    assert_eq!(
+        split_chain(r#"diff "a && b" 'c;d' e\;f"#).unwrap(),
+        [(Connector::Sequence, r#"diff "a && b" 'c;d' e\;f"#)]
+    );
+    assert!(split_chain("").unwrap().is_empty());
+}
+
+#[test]
+fn test_split_chain_errors() {
+    assert_eq!(
+        split_chain("&& about"),
+        Err(TokenizeError::MissingCommand(Connector::And))
+    );
+    assert_eq!(
+        split_chain("about ||"),
+        Err(TokenizeError::MissingCommand(Connector::Or))
+    );
+    assert_eq!(
+        split_chain("about ;; skills"),
+        Err(TokenizeError::MissingCommand(Connector::Sequence))
+    );
+    assert_eq!(
+        split_chain("diff 'a && b"),
+        Err(TokenizeError::UnterminatedQuote('\''))
+    );
+}
+
+#[test]
+fn test_strip_comment() {
+    assert_eq!(strip_comment("about # trailing"), "about ");
+    assert_eq!(strip_comment("# whole line"), "");
+    assert_eq!(strip_comment("about;# after ;"), "about;");
+    assert_eq!(
+        strip_comment(r#"diff 'a # b' "c # d" e\ #f g#h"#),
+        r#"diff 'a # b' "c # d" e\ #f g#h"#
+    );
+}
+
+#[test]
+fn test_tokenize_variables() {
+    let lookup = |name: &str| match name {
+        "NAME" => Some("Lady".to_string()),
+        "CMD" => Some("conway  --about".to_string()),
+        "?" => Some("0".to_string()),
+        _ => None,
+    };
+    assert_eq!(
+        tokenize("diff $NAME ${NAME}doodle $?", lookup).unwrap(),
+        ["diff", "Lady", "Ladydoodle", "0"]
+    );
+    // unquoted values are split into arguments, quoted ones aren't
+    assert_eq!(tokenize("$CMD", lookup).unwrap(), ["conway", "--about"]);
+    assert_eq!(tokenize(r#""$CMD""#, lookup).unwrap(), ["conway  --about"]);
+    assert_eq!(
+        tokenize(r#"'$NAME' \$NAME "\$NAME""#, lookup).unwrap(),
+        ["$NAME", "$NAME", "$NAME"]
+    );
+    // unknown variables are empty, and a lone `$` is literal
+    assert_eq!(
+        tokenize(r#"a $MISSING "$MISSING" $ 5$"#, lookup).unwrap(),
+        ["a", "", "$", "5$"]
+    );
+    assert_eq!(
+        tokenize("${NAME", lookup),
+        Err(TokenizeError::UnterminatedVariable)
+    );
+}
+
+#[test]
+fn test_quote() {
+    assert_eq!(quote("conway"), "conway");
+    assert_eq!(quote("--width=40"), "--width=40");
+    assert_eq!(quote(""), "''");
+    for arg in ["Hello World", "it's", r#"a "b" \c"#, "$HOME", "a;b && c"] {
+        assert_eq!(tokenize(&quote(arg), no_vars).unwrap(), [arg]);
+    }
+}
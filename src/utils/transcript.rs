@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+
+/// A single line entered into the terminal, along with the output of each command it ran
+#[derive(Debug, Clone)]
+pub struct Entry<T> {
+    /// Unique for the lifetime of the transcript, so entries can be used as keys when rendering
+    pub id: usize,
+    pub input: String,
+    pub outputs: Vec<T>,
+}
+
+/// A bounded scrollback of terminal entries, where the oldest entries are dropped first
+#[derive(Debug, Clone)]
+pub struct Transcript<T> {
+    entries: VecDeque<Entry<T>>,
+    capacity: usize,
+    next_id: usize,
+}
+
+impl<T> Transcript<T> {
+    pub fn new(capacity: usize) -> Self {
+        Transcript {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            next_id: 0,
+        }
+    }
+
+    pub fn push(&mut self, input: String, outputs: Vec<T>) {
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Entry {
+            id: self.next_id,
+            input,
+            outputs,
+        });
+        self.next_id += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Entry<T>> {
+        self.entries.iter()
+    }
+
+    pub fn latest(&self) -> Option<&Entry<T>> {
+        self.entries.back()
+    }
+}
+
+#[test]
+fn test_transcript_capacity() {
+    let mut transcript = Transcript::new(2);
+    transcript.push("about".into(), vec![()]);
+    transcript.push("skills".into(), vec![()]);
+    transcript.push("projects".into(), vec![()]);
+
+    let entries: Vec<_> = transcript
+        .entries()
+        .map(|e| (e.id, e.input.as_str()))
+        .collect();
+    assert_eq!(entries, [(1, "skills"), (2, "projects")]);
+
+    // ids keep counting up after clearing, so old keys are never reused
+    transcript.clear();
+    transcript.push("contact".into(), vec![]);
+    assert_eq!(transcript.latest().map(|e| e.id), Some(3));
+}
@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use crate::utils::tokenize::{is_variable_char, is_variable_start};
+
+/// Variables maintained by the terminal itself, which can't be set or unset
+pub const READ_ONLY: &[(&str, &str)] = &[
+    ("HISTCMD", "the number of entries in the history"),
+    (
+        "LAST_STATUS",
+        "whether the last command succeeded, as 'ok' or 'error'",
+    ),
+    (
+        "?",
+        "the last command's exit status, 0 on success and 1 on failure",
+    ),
+];
+
+/// Shell variables defined with `set`, which last for as long as the page is open
+#[derive(Debug, Clone, Default)]
+pub struct Variables {
+    vars: BTreeMap<String, String>,
+}
+
+impl Variables {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    pub fn vars(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn set(&mut self, name: &str, value: String) -> Result<(), String> {
+        check_writable(name)?;
+        self.vars.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Removes a variable, returning its value if it was set
+    pub fn remove(&mut self, name: &str) -> Result<Option<String>, String> {
+        check_writable(name)?;
+        Ok(self.vars.remove(name))
+    }
+}
+
+/// Splits an assignment like `NAME=value` into its name and value
+pub fn parse_assignment(assignment: &str) -> Result<(&str, &str), String> {
+    let (name, value) = assignment.split_once('=').ok_or_else(|| {
+        format!("expected an assignment in the form NAME=value, got '{assignment}'")
+    })?;
+    check_writable(name)?;
+    Ok((name, value))
+}
+
+fn check_writable(name: &str) -> Result<(), String> {
+    if READ_ONLY.iter().any(|(read_only, _)| *read_only == name) {
+        return Err(format!("'{name}' is a read-only variable"));
+    }
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(is_variable_start) && chars.all(is_variable_char);
+    if !valid {
+        return Err(format!("'{name}' is not a valid variable name"));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_set_variables() {
+    let mut vars = Variables::default();
+    vars.set("GREETING", "hello world".into()).unwrap();
+    vars.set("_count2", "2".into()).unwrap();
+    assert_eq!(vars.get("GREETING"), Some("hello world"));
+    assert_eq!(vars.remove("_count2"), Ok(Some("2".into())));
+    assert_eq!(vars.remove("_count2"), Ok(None));
+
+    assert!(vars.set("HISTCMD", "5".into()).is_err());
+    assert!(vars.set("HISTSIZE", "5".into()).is_ok());
+    assert!(vars.set("?", "0".into()).is_err());
+    assert!(vars.set("2FAST", "".into()).is_err());
+    assert!(vars.set("has-dash", "".into()).is_err());
+}
+
+#[test]
+fn test_parse_assignment() {
+    assert_eq!(parse_assignment("W=40"), Ok(("W", "40")));
+    assert_eq!(parse_assignment("EMPTY="), Ok(("EMPTY", "")));
+    assert_eq!(parse_assignment("EQ=a=b"), Ok(("EQ", "a=b")));
+    assert!(parse_assignment("W").is_err());
+    assert!(parse_assignment("LAST_STATUS=ok").is_err());
+}
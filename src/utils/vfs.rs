@@ -0,0 +1,250 @@
+//! A read-only filesystem over the portfolio's content, which the `ls`, `cd`, `cat` and `tree`
+//! commands browse. Paths are written like in unix, as in `/projects/prc/prc-rs.md`, where `~`
+//! is the same as the root.
+
+use std::sync::LazyLock;
+
+use crate::commands::contact::CONTACTS;
+use crate::components::about::{BIO, HOBBIES};
+use crate::content::{PROJECT_GROUPS, SKILLS, slug};
+
+pub const ROOT: &str = "/";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// A directory's entries, in the order they're listed
+    Dir(Vec<(String, Node)>),
+    File(String),
+}
+
+impl Node {
+    pub fn is_dir(&self) -> bool {
+        matches!(self, Node::Dir(_))
+    }
+
+    /// Gets the entries of a directory, which a file has none of
+    pub fn entries(&self) -> &[(String, Node)] {
+        match self {
+            Node::Dir(entries) => entries,
+            Node::File(_) => &[],
+        }
+    }
+
+    fn child(&self, name: &str) -> Option<&Node> {
+        self.entries()
+            .iter()
+            .find(|(entry, _)| entry == name)
+            .map(|(_, node)| node)
+    }
+}
+
+pub static FILESYSTEM: LazyLock<Node> = LazyLock::new(|| {
+    let projects = PROJECT_GROUPS
+        .iter()
+        .map(|group| {
+            let files = group
+                .projects
+                .iter()
+                .map(|project| {
+                    let mut text = format!("# {}\n\n{}\n", project.title, project.summary);
+                    if !project.bullets.is_empty() {
+                        text.push('\n');
+                    }
+                    for bullet in &project.bullets {
+                        text.push_str(&format!("- {bullet}\n"));
+                    }
+                    text.push('\n');
+                    if let Some(deployment) = &project.deployment {
+                        text.push_str(&format!("Deployment: {deployment}\n"));
+                    }
+                    for (label, url) in &project.sources {
+                        text.push_str(&format!("{label}: {url}\n"));
+                    }
+                    text.push('\n');
+                    for (label, value) in project.details() {
+                        text.push_str(&format!("{label}: {value}\n"));
+                    }
+                    text.push_str(&format!("Tags: {}\n", project.tags.join(", ")));
+                    (format!("{}.md", slug(&project.title)), Node::File(text))
+                })
+                .collect();
+            (group.name(), Node::Dir(files))
+        })
+        .collect();
+
+    let skills = SKILLS
+        .iter()
+        .map(|skill| {
+            let text: String = skill.bullets.iter().map(|b| format!("- {b}\n")).collect();
+            (slug(&skill.category), Node::File(text))
+        })
+        .collect();
+
+    let mut about = format!("# About\n\n{}\n\n", BIO.join("\n\n"));
+    for hobby in HOBBIES {
+        about.push_str(&format!("- {hobby}\n"));
+    }
+
+    let contact: String = CONTACTS
+        .iter()
+        .map(|contact| format!("{}\n{}\n", contact.heading, contact.label))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Node::Dir(vec![
+        ("about.md".to_string(), Node::File(about)),
+        ("contact.md".to_string(), Node::File(contact)),
+        ("projects".to_string(), Node::Dir(projects)),
+        ("skills".to_string(), Node::Dir(skills)),
+    ])
+});
+
+/// Gets the path of the file listing a category of skills
+pub fn skill_path(category: &str) -> String {
+    format!("/skills/{}", slug(category))
+}
+
+/// Resolves a path from the directory `cwd` into an absolute path, without checking that it
+/// exists. `..` at the root stays at the root.
+pub fn resolve(cwd: &str, path: &str) -> String {
+    let (start, rest) = match path {
+        "~" => (ROOT, ""),
+        _ if path.starts_with('/') => (ROOT, path),
+        _ => match path.strip_prefix("~/") {
+            Some(rest) => (ROOT, rest),
+            None => (cwd, path),
+        },
+    };
+    let mut parts: Vec<&str> = Vec::new();
+    for part in start.split('/').chain(rest.split('/')) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    format!("/{}", parts.join("/"))
+}
+
+/// Joins a name onto an absolute path
+pub fn join(dir: &str, name: &str) -> String {
+    format!("{}/{name}", dir.trim_end_matches('/'))
+}
+
+/// Finds the node at an absolute path
+pub fn lookup(path: &str) -> Option<&'static Node> {
+    path.split('/')
+        .filter(|part| !part.is_empty())
+        .try_fold(&*FILESYSTEM, |node, name| node.child(name))
+}
+
+/// Resolves a path from `cwd` and finds its node, returning the absolute path along with it
+pub fn open(cwd: &str, path: &str) -> Result<(String, &'static Node), String> {
+    let absolute = resolve(cwd, path);
+    match lookup(&absolute) {
+        Some(node) => Ok((absolute, node)),
+        None => Err(format!("{path}: no such file or directory")),
+    }
+}
+
+/// Lists every path below a directory, relative to it, with a `/` after each directory
+pub fn descendants(dir: &Node) -> Vec<(String, &Node)> {
+    let mut paths = Vec::new();
+    for (name, node) in dir.entries() {
+        if node.is_dir() {
+            paths.push((format!("{name}/"), node));
+            paths.extend(
+                descendants(node)
+                    .into_iter()
+                    .map(|(path, node)| (format!("{name}/{path}"), node)),
+            );
+        } else {
+            paths.push((name.clone(), node));
+        }
+    }
+    paths
+}
+
+/// Draws a directory and everything in it as a tree, like the `tree` program
+pub fn tree(name: &str, node: &Node) -> String {
+    fn draw(node: &Node, indent: &str, lines: &mut Vec<String>, counts: &mut (usize, usize)) {
+        let entries = node.entries();
+        for (i, (name, child)) in entries.iter().enumerate() {
+            let last = i + 1 == entries.len();
+            let (branch, next) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            if child.is_dir() {
+                counts.0 += 1;
+                lines.push(format!("{indent}{branch}{name}/"));
+                draw(child, &format!("{indent}{next}"), lines, counts);
+            } else {
+                counts.1 += 1;
+                lines.push(format!("{indent}{branch}{name}"));
+            }
+        }
+    }
+
+    let mut lines = vec![name.to_string()];
+    let mut counts = (0, 0);
+    draw(node, "", &mut lines, &mut counts);
+    if node.is_dir() {
+        let (dirs, files) = counts;
+        let dirs_word = if dirs == 1 {
+            "directory"
+        } else {
+            "directories"
+        };
+        let files_word = if files == 1 { "file" } else { "files" };
+        lines.push(String::new());
+        lines.push(format!("{dirs} {dirs_word}, {files} {files_word}"));
+    }
+    lines.join("\n")
+}
+
+#[test]
+fn test_resolve() {
+    assert_eq!(resolve("/", "projects"), "/projects");
+    assert_eq!(
+        resolve("/projects", "prc/prc-rs.md"),
+        "/projects/prc/prc-rs.md"
+    );
+    assert_eq!(resolve("/projects/prc", ".."), "/projects");
+    assert_eq!(resolve("/projects/prc", "../../skills/./"), "/skills");
+    assert_eq!(resolve("/projects", "/skills"), "/skills");
+    assert_eq!(resolve("/projects", "~"), "/");
+    assert_eq!(resolve("/projects", "~/about.md"), "/about.md");
+    assert_eq!(resolve("/", "../.."), "/");
+}
+
+#[test]
+fn test_lookup() {
+    assert!(lookup("/").is_some_and(Node::is_dir));
+    assert!(lookup("/skills/languages").is_some_and(|node| !node.is_dir()));
+    let Some(Node::File(text)) = lookup("/projects/prc/prc-rs.md") else {
+        panic!("prc-rs.md should be a file");
+    };
+    assert!(text.starts_with("# prc-rs\n"));
+    assert!(lookup("/projects/web/this-portfolio.md").is_some());
+    assert!(lookup(&skill_path("CI / CD")).is_some());
+    assert!(lookup("/about.md/nope").is_none());
+    assert_eq!(
+        open("/projects", "nope").unwrap_err(),
+        "nope: no such file or directory"
+    );
+}
+
+#[test]
+fn test_tree() {
+    let skills = lookup("/skills").unwrap();
+    let drawn = tree("skills", skills);
+    let lines: Vec<&str> = drawn.lines().collect();
+    assert_eq!(lines[..3], ["skills", "├── languages", "├── frontend"]);
+    assert_eq!(lines[lines.len() - 3], "└── testing");
+    assert_eq!(lines[lines.len() - 1], "0 directories, 9 files");
+    assert!(tree("/", &FILESYSTEM).contains("│   ├── prc/"));
+}